@@ -0,0 +1,1127 @@
+//! A double-ended queue with fixed capacity, and its iterators.
+
+use std::cmp;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::mem::MaybeUninit;
+use std::ops::{Index, IndexMut, RangeBounds};
+use std::ptr;
+use std::slice;
+
+#[cfg(feature="serde")]
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+use crate::LenUint;
+use crate::errors::CapacityError;
+use crate::utils::{CapacityLimit, MakeMaybeUninit, range_to_indices};
+
+/// A double-ended queue with a fixed capacity.
+///
+/// The `ArrayDeque` is a ring buffer backed by a fixed size array. Elements
+/// can be pushed and popped at both ends in O(1) time. The `ArrayDeque<T, CAP>`
+/// is parameterized by `T` for the element type and `CAP` for the maximum capacity.
+///
+/// `CAP` is of type `usize` but is range limited to `u32::MAX` (or `u16::MAX` on 16-bit targets);
+/// attempting to create larger deques with larger capacity is a compile time error.
+///
+/// The elements are not necessarily stored contiguously; see [`as_slices`] and
+/// [`make_contiguous`] for slice access.
+///
+/// [`as_slices`]: ArrayDeque::as_slices
+/// [`make_contiguous`]: ArrayDeque::make_contiguous
+#[repr(C)]
+pub struct ArrayDeque<T, const CAP: usize> {
+    head: LenUint,
+    len: LenUint,
+    // the `len` elements starting at `head` (wrapping around at `CAP`) are initialized
+    xs: [MaybeUninit<T>; CAP],
+}
+
+impl<T, const CAP: usize> Drop for ArrayDeque<T, CAP> {
+    fn drop(&mut self) {
+        self.clear();
+
+        // MaybeUninit inhibits array's drop
+    }
+}
+
+/// Drops a slice of elements when dropped; used to keep dropping the second
+/// part of the deque if dropping an element of the first part panics.
+struct Dropper<T>(*mut [T]);
+
+impl<T> Drop for Dropper<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.0);
+        }
+    }
+}
+
+impl<T, const CAP: usize> ArrayDeque<T, CAP> {
+    /// Create a new empty `ArrayDeque`.
+    ///
+    /// The maximum capacity is given by the generic parameter `CAP`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 16>::new();
+    /// deque.push_back(1);
+    /// deque.push_front(2);
+    /// assert_eq!(deque.pop_back(), Some(1));
+    /// assert_eq!(deque.capacity(), 16);
+    /// ```
+    #[inline]
+    pub fn new() -> ArrayDeque<T, CAP> {
+        let () = CapacityLimit::<CAP, LenUint>::CHECK;
+        unsafe {
+            ArrayDeque { xs: MaybeUninit::uninit().assume_init(), head: 0, len: 0 }
+        }
+    }
+
+    /// Create a new empty `ArrayDeque` (const fn).
+    ///
+    /// The maximum capacity is given by the generic parameter `CAP`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// static DEQUE: ArrayDeque<u8, 1024> = ArrayDeque::new_const();
+    /// ```
+    pub const fn new_const() -> ArrayDeque<T, CAP> {
+        let () = CapacityLimit::<CAP, LenUint>::CHECK;
+        ArrayDeque { xs: MakeMaybeUninit::ARRAY, head: 0, len: 0 }
+    }
+
+    /// Return the number of elements in the `ArrayDeque`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2, 3]);
+    /// deque.pop_front();
+    /// assert_eq!(deque.len(), 2);
+    /// ```
+    #[inline(always)]
+    pub const fn len(&self) -> usize { self.len as usize }
+
+    /// Returns whether the `ArrayDeque` is empty.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1]);
+    /// deque.pop_back();
+    /// assert_eq!(deque.is_empty(), true);
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Return the capacity of the `ArrayDeque`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let deque = ArrayDeque::from([1, 2, 3]);
+    /// assert_eq!(deque.capacity(), 3);
+    /// ```
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize { CAP }
+
+    /// Return true if the `ArrayDeque` is completely filled to its capacity, false otherwise.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 1>::new();
+    /// assert!(!deque.is_full());
+    /// deque.push_back(1);
+    /// assert!(deque.is_full());
+    /// ```
+    pub const fn is_full(&self) -> bool { self.len() == self.capacity() }
+
+    /// Returns the capacity left in the `ArrayDeque`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2, 3]);
+    /// deque.pop_front();
+    /// assert_eq!(deque.remaining_capacity(), 1);
+    /// ```
+    pub const fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Return the physical index in the buffer of the element at logical `index`.
+    ///
+    /// `index` must be at most `CAP`.
+    #[inline(always)]
+    fn to_physical(&self, index: usize) -> usize {
+        debug_assert!(index <= CAP);
+        let i = self.head as usize + index;
+        if i >= CAP { i - CAP } else { i }
+    }
+
+    /// Return a raw pointer to the buffer.
+    fn ptr(&self) -> *const T {
+        self.xs.as_ptr() as _
+    }
+
+    /// Return a raw mutable pointer to the buffer.
+    fn ptr_mut(&mut self) -> *mut T {
+        self.xs.as_mut_ptr() as _
+    }
+
+    /// Push `element` to the back of the deque.
+    ///
+    /// ***Panics*** if the deque is already full.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 2>::new();
+    ///
+    /// deque.push_back(1);
+    /// deque.push_back(2);
+    ///
+    /// assert_eq!(deque.as_slices(), (&[1, 2][..], &[][..]));
+    /// ```
+    #[track_caller]
+    pub fn push_back(&mut self, element: T) {
+        self.try_push_back(element).unwrap()
+    }
+
+    /// Push `element` to the back of the deque.
+    ///
+    /// Return `Ok` if the push succeeds, or return an error if the deque
+    /// is already full.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 2>::new();
+    ///
+    /// assert!(deque.try_push_back(1).is_ok());
+    /// assert!(deque.try_push_back(2).is_ok());
+    /// let overflow = deque.try_push_back(3);
+    ///
+    /// assert_eq!(overflow.unwrap_err().element(), 3);
+    /// ```
+    pub fn try_push_back(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(element));
+        }
+        unsafe {
+            let index = self.to_physical(self.len());
+            ptr::write(self.ptr_mut().add(index), element);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Push `element` to the front of the deque.
+    ///
+    /// ***Panics*** if the deque is already full.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 2>::new();
+    ///
+    /// deque.push_front(1);
+    /// deque.push_front(2);
+    ///
+    /// assert_eq!(deque.front(), Some(&2));
+    /// ```
+    #[track_caller]
+    pub fn push_front(&mut self, element: T) {
+        self.try_push_front(element).unwrap()
+    }
+
+    /// Push `element` to the front of the deque.
+    ///
+    /// Return `Ok` if the push succeeds, or return an error if the deque
+    /// is already full.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 2>::new();
+    ///
+    /// assert!(deque.try_push_front(1).is_ok());
+    /// assert!(deque.try_push_front(2).is_ok());
+    /// let overflow = deque.try_push_front(3);
+    ///
+    /// assert_eq!(overflow.unwrap_err().element(), 3);
+    /// ```
+    pub fn try_push_front(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(element));
+        }
+        let head = if self.head == 0 { CAP - 1 } else { self.head as usize - 1 };
+        unsafe {
+            ptr::write(self.ptr_mut().add(head), element);
+        }
+        self.head = head as LenUint;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Push `element` to the back of the deque, removing the front element
+    /// to make room if the deque is full.
+    ///
+    /// Return the evicted element if there was one. For a deque with zero
+    /// capacity, `element` itself is returned.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 3>::new();
+    ///
+    /// for i in 0..3 {
+    ///     assert_eq!(deque.push_back_overwrite(i), None);
+    /// }
+    /// assert_eq!(deque.push_back_overwrite(3), Some(0));
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    /// ```
+    pub fn push_back_overwrite(&mut self, element: T) -> Option<T> {
+        if CAP == 0 {
+            return Some(element);
+        }
+        let evicted = if self.is_full() { self.pop_front() } else { None };
+        unsafe {
+            let index = self.to_physical(self.len());
+            ptr::write(self.ptr_mut().add(index), element);
+        }
+        self.len += 1;
+        evicted
+    }
+
+    /// Remove the first element of the deque and return it.
+    ///
+    /// Return `Some(` *element* `)` if the deque is non-empty, else `None`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2]);
+    ///
+    /// assert_eq!(deque.pop_front(), Some(1));
+    /// assert_eq!(deque.pop_front(), Some(2));
+    /// assert_eq!(deque.pop_front(), None);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let head = self.head as usize;
+        self.head = self.to_physical(1) as LenUint;
+        self.len -= 1;
+        unsafe {
+            Some(ptr::read(self.ptr().add(head)))
+        }
+    }
+
+    /// Remove the last element of the deque and return it.
+    ///
+    /// Return `Some(` *element* `)` if the deque is non-empty, else `None`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2]);
+    ///
+    /// assert_eq!(deque.pop_back(), Some(2));
+    /// assert_eq!(deque.pop_back(), Some(1));
+    /// assert_eq!(deque.pop_back(), None);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        unsafe {
+            let index = self.to_physical(self.len());
+            Some(ptr::read(self.ptr().add(index)))
+        }
+    }
+
+    /// Return a reference to the element at `index`, counted from the front,
+    /// or `None` if the index is out of bounds.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 4>::new();
+    /// deque.push_back(2);
+    /// deque.push_front(1);
+    ///
+    /// assert_eq!(deque.get(0), Some(&1));
+    /// assert_eq!(deque.get(1), Some(&2));
+    /// assert_eq!(deque.get(2), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            unsafe {
+                Some(&*self.ptr().add(self.to_physical(index)))
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Return a mutable reference to the element at `index`, counted from the
+    /// front, or `None` if the index is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len() {
+            unsafe {
+                let index = self.to_physical(index);
+                Some(&mut *self.ptr_mut().add(index))
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Return a reference to the front element, or `None` if the deque is empty.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Return a mutable reference to the front element, or `None` if the
+    /// deque is empty.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Return a reference to the back element, or `None` if the deque is empty.
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len().wrapping_sub(1))
+    }
+
+    /// Return a mutable reference to the back element, or `None` if the
+    /// deque is empty.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len().wrapping_sub(1))
+    }
+
+    /// Return the contents of the deque as a pair of slices, which in order
+    /// contain all elements from front to back.
+    ///
+    /// If the elements are stored contiguously, the second slice is empty.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 4>::new();
+    /// deque.push_back(2);
+    /// deque.push_back(3);
+    /// deque.push_front(1);
+    ///
+    /// assert_eq!(deque.as_slices(), (&[1][..], &[2, 3][..]));
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let head = self.head as usize;
+        let len = self.len();
+        let ptr = self.ptr();
+        unsafe {
+            if head + len <= CAP {
+                (slice::from_raw_parts(ptr.add(head), len), &[])
+            } else {
+                let front_len = CAP - head;
+                (slice::from_raw_parts(ptr.add(head), front_len),
+                 slice::from_raw_parts(ptr, len - front_len))
+            }
+        }
+    }
+
+    /// Return the contents of the deque as a pair of mutable slices, which in
+    /// order contain all elements from front to back.
+    ///
+    /// If the elements are stored contiguously, the second slice is empty.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let head = self.head as usize;
+        let len = self.len();
+        let ptr = self.ptr_mut();
+        unsafe {
+            if head + len <= CAP {
+                (slice::from_raw_parts_mut(ptr.add(head), len), &mut [])
+            } else {
+                let front_len = CAP - head;
+                (slice::from_raw_parts_mut(ptr.add(head), front_len),
+                 slice::from_raw_parts_mut(ptr, len - front_len))
+            }
+        }
+    }
+
+    /// Rearrange the buffer so that the elements are stored contiguously,
+    /// and return them as a mutable slice.
+    ///
+    /// The order of the elements is preserved.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 4>::new();
+    /// deque.push_back(2);
+    /// deque.push_back(3);
+    /// deque.push_front(1);
+    ///
+    /// assert_eq!(deque.make_contiguous(), &[1, 2, 3]);
+    /// assert_eq!(deque.as_slices(), (&[1, 2, 3][..], &[][..]));
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        let head = self.head as usize;
+        let len = self.len();
+        if head + len > CAP {
+            // Rotating the whole buffer moves the element at physical index
+            // `head` to index 0; the uninitialized slots are moved as
+            // `MaybeUninit` values, which is fine.
+            self.xs.rotate_left(head);
+            self.head = 0;
+        }
+        let head = self.head as usize;
+        unsafe {
+            slice::from_raw_parts_mut(self.ptr_mut().add(head), len)
+        }
+    }
+
+    /// Return an iterator over references to the elements, from front to back.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 4>::new();
+    /// deque.push_back(2);
+    /// deque.push_front(1);
+    ///
+    /// let mut iter = deque.iter();
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next(), Some(&2));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        Iter { front: front.iter(), back: back.iter() }
+    }
+
+    /// Return an iterator over mutable references to the elements, from front
+    /// to back.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+        IterMut { front: front.iter_mut(), back: back.iter_mut() }
+    }
+
+    /// Shortens the deque, keeping the first `len` elements and dropping
+    /// the rest.
+    ///
+    /// If `len` is greater than the deque’s current length this has no
+    /// effect.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2, 3, 4, 5]);
+    /// deque.truncate(3);
+    /// assert_eq!(deque.as_slices(), (&[1, 2, 3][..], &[][..]));
+    /// ```
+    pub fn truncate(&mut self, new_len: usize) {
+        let len = self.len();
+        if new_len >= len {
+            return;
+        }
+        let (front, back) = self.as_mut_slices();
+        let (front, back) = if new_len < front.len() {
+            (&mut front[new_len..] as *mut [T], back as *mut [T])
+        } else {
+            let start = new_len - front.len();
+            (&mut [][..] as *mut [T], &mut back[start..] as *mut [T])
+        };
+        // panic safety: set length before dropping elements.
+        self.len = new_len as LenUint;
+        let _back_dropper = Dropper(back);
+        unsafe {
+            ptr::drop_in_place(front);
+        }
+    }
+
+    /// Remove all elements in the deque.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+        self.head = 0;
+    }
+
+    /// Create a draining iterator that removes the specified range in the deque
+    /// and yields the removed items from front to back. The element range is
+    /// removed even if the iterator is not consumed until the end.
+    ///
+    /// Note: It is unspecified how many elements are removed from the deque,
+    /// if the `Drain` value is leaked.
+    ///
+    /// **Panics** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2, 3, 4]);
+    /// let drained: Vec<_> = deque.drain(1..3).collect();
+    /// assert_eq!(drained, [2, 3]);
+    /// assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 4]);
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, CAP>
+        where R: RangeBounds<usize>
+    {
+        let len = self.len();
+        let (start, end) = range_to_indices(&range, len);
+        if start > end {
+            panic!("ArrayDeque::drain: start index {} is greater than end index {}", start, end);
+        }
+        if end > len {
+            panic!("ArrayDeque::drain: end index {} is out of bounds in deque of length {}", end, len);
+        }
+
+        // Memory safety: as in `ArrayVec::drain`, the length is shortened to
+        // `start` for the lifetime of the `Drain`, so that no moved-from
+        // elements are accessible if the destructor never runs.
+        self.len = start as LenUint;
+        Drain {
+            idx: start,
+            end,
+            tail_start: end,
+            tail_len: len - end,
+            deque: self as *mut _,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, const CAP: usize> Index<usize> for ArrayDeque<T, CAP> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        self.get(index).unwrap_or_else(|| {
+            panic!("ArrayDeque: index {} is out of bounds in deque of length {}", index, len)
+        })
+    }
+}
+
+impl<T, const CAP: usize> IndexMut<usize> for ArrayDeque<T, CAP> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("ArrayDeque: index {} is out of bounds in deque of length {}", index, len)
+        })
+    }
+}
+
+/// Create an `ArrayDeque` from an array.
+///
+/// ```
+/// use arrayvec::ArrayDeque;
+///
+/// let deque = ArrayDeque::from([1, 2, 3]);
+/// assert_eq!(deque.len(), 3);
+/// assert_eq!(deque.capacity(), 3);
+/// ```
+impl<T, const CAP: usize> From<[T; CAP]> for ArrayDeque<T, CAP> {
+    fn from(array: [T; CAP]) -> Self {
+        let () = CapacityLimit::<CAP, LenUint>::CHECK;
+        let array = ManuallyDrop::new(array);
+        let mut deque = <ArrayDeque<T, CAP>>::new();
+        unsafe {
+            (&*array as *const [T; CAP] as *const [MaybeUninit<T>; CAP])
+                .copy_to_nonoverlapping(&mut deque.xs as *mut [MaybeUninit<T>; CAP], 1);
+        }
+        deque.len = CAP as LenUint;
+        deque
+    }
+}
+
+/// Iterate the `ArrayDeque` with references to each element.
+impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a ArrayDeque<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+/// Iterate the `ArrayDeque` with mutable references to each element.
+impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a mut ArrayDeque<T, CAP> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+}
+
+/// Iterate the `ArrayDeque` with each element by value.
+///
+/// The deque is consumed by this operation.
+///
+/// ```
+/// use arrayvec::ArrayDeque;
+///
+/// for elt in ArrayDeque::from([1, 2, 3]) {
+///     // ...
+/// }
+/// ```
+impl<T, const CAP: usize> IntoIterator for ArrayDeque<T, CAP> {
+    type Item = T;
+    type IntoIter = IntoIter<T, CAP>;
+    fn into_iter(self) -> IntoIter<T, CAP> {
+        IntoIter { deque: self }
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn extend_panic() {
+    panic!("ArrayDeque: capacity exceeded in extend/from_iter");
+}
+
+/// Extend the `ArrayDeque` with an iterator, pushing to the back.
+///
+/// ***Panics*** if extending the deque exceeds its capacity.
+impl<T, const CAP: usize> Extend<T> for ArrayDeque<T, CAP> {
+    /// Extend the `ArrayDeque` with an iterator, pushing to the back.
+    ///
+    /// ***Panics*** if extending the deque exceeds its capacity.
+    #[track_caller]
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for elt in iter {
+            if self.try_push_back(elt).is_err() {
+                extend_panic();
+            }
+        }
+    }
+}
+
+/// Create an `ArrayDeque` from an iterator.
+///
+/// ***Panics*** if the number of elements in the iterator exceeds the deque's capacity.
+impl<T, const CAP: usize> iter::FromIterator<T> for ArrayDeque<T, CAP> {
+    /// Create an `ArrayDeque` from an iterator.
+    ///
+    /// ***Panics*** if the number of elements in the iterator exceeds the deque's capacity.
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut deque = ArrayDeque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T, const CAP: usize> Clone for ArrayDeque<T, CAP>
+    where T: Clone
+{
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T, const CAP: usize> Hash for ArrayDeque<T, CAP>
+    where T: Hash
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash element by element, so that the hasher input does not depend
+        // on where the ring buffer wraps around
+        self.len().hash(state);
+        self.iter().for_each(|elt| elt.hash(state));
+    }
+}
+
+impl<T, const CAP: usize> PartialEq for ArrayDeque<T, CAP>
+    where T: PartialEq
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, const CAP: usize> Eq for ArrayDeque<T, CAP> where T: Eq { }
+
+impl<T, const CAP: usize> PartialOrd for ArrayDeque<T, CAP> where T: PartialOrd {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T, const CAP: usize> Ord for ArrayDeque<T, CAP> where T: Ord {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T, const CAP: usize> fmt::Debug for ArrayDeque<T, CAP> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const CAP: usize> Default for ArrayDeque<T, CAP> {
+    /// Return an empty deque
+    fn default() -> ArrayDeque<T, CAP> {
+        ArrayDeque::new()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+/// "Best efforts" zeroing of the `ArrayDeque`'s buffer when the `zeroize` feature is enabled.
+///
+/// The length is set to 0, and the buffer is dropped and zeroized.
+/// Cannot ensure that previous moves of the `ArrayDeque` did not leave values on the stack.
+///
+/// ```
+/// use arrayvec::ArrayDeque;
+/// use zeroize::Zeroize;
+/// let mut deque = ArrayDeque::from([1, 2, 3]);
+/// deque.zeroize();
+/// assert_eq!(deque.len(), 0);
+/// ```
+impl<Z: zeroize::Zeroize, const CAP: usize> zeroize::Zeroize for ArrayDeque<Z, CAP> {
+    fn zeroize(&mut self) {
+        // Zeroize all the contained elements.
+        let (front, back) = self.as_mut_slices();
+        front.iter_mut().zeroize();
+        back.iter_mut().zeroize();
+        // Drop all the elements and set the length to 0.
+        self.clear();
+        // Zeroize the backing array.
+        self.xs.zeroize();
+    }
+}
+
+/// An iterator over references to the elements of an `ArrayDeque`.
+pub struct Iter<'a, T: 'a> {
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.front.next() {
+            Some(elt) => Some(elt),
+            None => self.back.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.back.next_back() {
+            Some(elt) => Some(elt),
+            None => self.front.next_back(),
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> { }
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter { front: self.front.clone(), back: self.back.clone() }
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Iter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Iter")
+            .field(&self.front)
+            .field(&self.back)
+            .finish()
+    }
+}
+
+/// An iterator over mutable references to the elements of an `ArrayDeque`.
+pub struct IterMut<'a, T: 'a> {
+    front: slice::IterMut<'a, T>,
+    back: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.front.next() {
+            Some(elt) => Some(elt),
+            None => self.back.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.back.next_back() {
+            Some(elt) => Some(elt),
+            None => self.front.next_back(),
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> { }
+
+impl<'a, T: fmt::Debug> fmt::Debug for IterMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IterMut")
+            .field(&self.front)
+            .field(&self.back)
+            .finish()
+    }
+}
+
+/// By-value iterator for `ArrayDeque`.
+pub struct IntoIter<T, const CAP: usize> {
+    deque: ArrayDeque<T, CAP>,
+}
+
+impl<T, const CAP: usize> IntoIter<T, CAP> {
+    /// Returns the remaining items of this iterator as a pair of slices.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.deque.as_slices()
+    }
+
+    /// Returns the remaining items of this iterator as a pair of mutable slices.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        self.deque.as_mut_slices()
+    }
+}
+
+impl<T, const CAP: usize> Iterator for IntoIter<T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.deque.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for IntoIter<T, CAP> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, const CAP: usize> ExactSizeIterator for IntoIter<T, CAP> { }
+
+impl<T, const CAP: usize> Clone for IntoIter<T, CAP>
+where T: Clone,
+{
+    fn clone(&self) -> IntoIter<T, CAP> {
+        IntoIter { deque: self.deque.clone() }
+    }
+}
+
+impl<T, const CAP: usize> fmt::Debug for IntoIter<T, CAP>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.deque.iter())
+            .finish()
+    }
+}
+
+/// A draining iterator for `ArrayDeque`.
+pub struct Drain<'a, T: 'a, const CAP: usize> {
+    /// Logical index of the next element to yield from the front
+    idx: usize,
+    /// Logical end (exclusive) of the elements left to yield
+    end: usize,
+    /// Logical index of tail to preserve
+    tail_start: usize,
+    /// Length of tail
+    tail_len: usize,
+    deque: *mut ArrayDeque<T, CAP>,
+    marker: PhantomData<&'a mut ArrayDeque<T, CAP>>,
+}
+
+unsafe impl<'a, T: Sync, const CAP: usize> Sync for Drain<'a, T, CAP> {}
+unsafe impl<'a, T: Send, const CAP: usize> Send for Drain<'a, T, CAP> {}
+
+impl<'a, T: 'a, const CAP: usize> Drain<'a, T, CAP> {
+    /// Move out the element at logical `index`.
+    unsafe fn read(&mut self, index: usize) -> T {
+        let deque = &mut *self.deque;
+        let index = deque.to_physical(index);
+        ptr::read(deque.ptr().add(index))
+    }
+}
+
+impl<'a, T: 'a, const CAP: usize> Iterator for Drain<'a, T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx == self.end {
+            None
+        } else {
+            let index = self.idx;
+            self.idx += 1;
+            unsafe {
+                Some(self.read(index))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: 'a, const CAP: usize> DoubleEndedIterator for Drain<'a, T, CAP>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx == self.end {
+            None
+        } else {
+            self.end -= 1;
+            let index = self.end;
+            unsafe {
+                Some(self.read(index))
+            }
+        }
+    }
+}
+
+impl<'a, T: 'a, const CAP: usize> ExactSizeIterator for Drain<'a, T, CAP> {}
+
+impl<'a, T: 'a, const CAP: usize> Drop for Drain<'a, T, CAP> {
+    fn drop(&mut self) {
+        // len is currently `start` so panicking while dropping will not cause a double drop.
+
+        // exhaust self first
+        for _ in self.by_ref() { }
+
+        unsafe {
+            let deque = &mut *self.deque;
+            let start = deque.len();
+            let drained = self.tail_start - start;
+            if drained != 0 {
+                // close the gap by moving the shorter of the front (before
+                // `start`) and the tail, element by element, since both may
+                // wrap around. Draining a prefix or suffix moves nothing.
+                let ptr = deque.ptr_mut();
+                if start < self.tail_len {
+                    for i in (0..start).rev() {
+                        let src = deque.to_physical(i);
+                        let dst = deque.to_physical(i + drained);
+                        ptr::copy_nonoverlapping(ptr.add(src), ptr.add(dst), 1);
+                    }
+                    deque.head = deque.to_physical(drained) as LenUint;
+                } else {
+                    for i in 0..self.tail_len {
+                        let src = deque.to_physical(self.tail_start + i);
+                        let dst = deque.to_physical(start + i);
+                        ptr::copy_nonoverlapping(ptr.add(src), ptr.add(dst), 1);
+                    }
+                }
+            }
+            deque.len = (start + self.tail_len) as LenUint;
+        }
+    }
+}
+
+#[cfg(feature="serde")]
+/// Requires crate feature `"serde"`
+impl<T: Serialize, const CAP: usize> Serialize for ArrayDeque<T, CAP> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.collect_seq(self)
+    }
+}
+
+#[cfg(feature="serde")]
+/// Requires crate feature `"serde"`
+impl<'de, T: Deserialize<'de>, const CAP: usize> Deserialize<'de> for ArrayDeque<T, CAP> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        use serde::de::{Visitor, SeqAccess, Error};
+
+        struct ArrayDequeVisitor<'de, T: Deserialize<'de>, const CAP: usize>(PhantomData<(&'de (), [T; CAP])>);
+
+        impl<'de, T: Deserialize<'de>, const CAP: usize> Visitor<'de> for ArrayDequeVisitor<'de, T, CAP> {
+            type Value = ArrayDeque<T, CAP>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an array with no more than {} items", CAP)
+            }
+
+            fn visit_seq<SA>(self, mut seq: SA) -> Result<Self::Value, SA::Error>
+                where SA: SeqAccess<'de>,
+            {
+                let mut values = ArrayDeque::<T, CAP>::new();
+
+                while let Some(value) = seq.next_element()? {
+                    if values.try_push_back(value).is_err() {
+                        return Err(SA::Error::invalid_length(CAP + 1, &self));
+                    }
+                }
+
+                Ok(values)
+            }
+        }
+
+        deserializer.deserialize_seq(ArrayDequeVisitor::<T, CAP>(PhantomData))
+    }
+}
+
+#[cfg(feature = "borsh")]
+/// Requires crate feature `"borsh"`
+impl<T, const CAP: usize> borsh::BorshSerialize for ArrayDeque<T, CAP>
+where
+    T: borsh::BorshSerialize,
+{
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        // Same encoding as a slice: u32 length followed by the elements
+        <u32 as borsh::BorshSerialize>::serialize(&(self.len() as u32), writer)?;
+        for elt in self {
+            elt.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "borsh")]
+/// Requires crate feature `"borsh"`
+impl<T, const CAP: usize> borsh::BorshDeserialize for ArrayDeque<T, CAP>
+where
+    T: borsh::BorshDeserialize,
+{
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let mut values = Self::new();
+        let len = <u32 as borsh::BorshDeserialize>::deserialize_reader(reader)?;
+        for _ in 0..len {
+            let elem = <T as borsh::BorshDeserialize>::deserialize_reader(reader)?;
+            if values.try_push_back(elem).is_err() {
+                return Err(borsh::io::Error::new(
+                    borsh::io::ErrorKind::InvalidData,
+                    format!("Expected an array with no more than {} items", CAP),
+                ));
+            }
+        }
+
+        Ok(values)
+    }
+}
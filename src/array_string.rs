@@ -3,6 +3,7 @@ use std::cmp;
 use std::convert::TryFrom;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::mem;
 use std::mem::MaybeUninit;
 use std::ops::{Deref, DerefMut};
 #[cfg(feature="std")]
@@ -14,9 +15,11 @@ use std::str::FromStr;
 use std::str::Utf8Error;
 
 use crate::CapacityError;
+use crate::FormatError;
 use crate::LenUint;
+use crate::len_type::{LenType, len_from_usize, len_to_usize};
 use crate::char::encode_utf8;
-use crate::utils::MakeMaybeUninit;
+use crate::utils::{CapacityFits, CapacityLimit, MakeMaybeUninit};
 
 #[cfg(feature="serde")]
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
@@ -25,30 +28,38 @@ use serde::{Serialize, Deserialize, Serializer, Deserializer};
 /// A string with a fixed capacity.
 ///
 /// The `ArrayString` is a string backed by a fixed size array. It keeps track
-/// of its length, and is parameterized by `CAP` for the maximum capacity.
+/// of its length, and is parameterized by `CAP` for the maximum capacity and the optional `L`
+/// for the [`LenType`](crate::LenType) that stores the length.
 ///
-/// `CAP` is of type `usize` but is range limited to `u32::MAX` (or `u16` on 16-bit targets);
-/// attempting to create larger arrayvecs with larger capacity will panic.
+/// `CAP` is of type `usize` but is range limited to the maximum of `L`, which is `u32::MAX` by
+/// default (or `u16` on 16-bit targets); attempting to create larger strings with larger capacity
+/// is a compile time error.
+///
+/// ```compile_fail
+/// use arrayvec::ArrayString;
+///
+/// let string = ArrayString::<{ u32::MAX as usize + 1 }>::new();
+/// ```
 ///
 /// The string is a contiguous value that you can store directly on the stack
 /// if needed.
 #[derive(Copy)]
 #[repr(C)]
-pub struct ArrayString<const CAP: usize> {
+pub struct ArrayString<const CAP: usize, L: LenType = LenUint> {
     // the `len` first elements of the array are initialized
-    len: LenUint,
+    len: L,
     xs: [MaybeUninit<u8>; CAP],
 }
 
-impl<const CAP: usize> Default for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> Default for ArrayString<CAP, L>
 {
     /// Return an empty `ArrayString`
-    fn default() -> ArrayString<CAP> {
+    fn default() -> ArrayString<CAP, L> {
         ArrayString::new()
     }
 }
 
-impl<const CAP: usize> ArrayString<CAP>
+impl<const CAP: usize, L: LenType> ArrayString<CAP, L>
 {
     /// Create a new empty `ArrayString`.
     ///
@@ -62,10 +73,10 @@ impl<const CAP: usize> ArrayString<CAP>
     /// assert_eq!(&string[..], "foo");
     /// assert_eq!(string.capacity(), 16);
     /// ```
-    pub fn new() -> ArrayString<CAP> {
-        assert_capacity_limit!(CAP);
+    pub fn new() -> ArrayString<CAP, L> {
+        let () = CapacityLimit::<CAP, L>::CHECK;
         unsafe {
-            ArrayString { xs: MaybeUninit::uninit().assume_init(), len: 0 }
+            ArrayString { xs: MaybeUninit::uninit().assume_init(), len: len_from_usize(0) }
         }
     }
 
@@ -78,14 +89,50 @@ impl<const CAP: usize> ArrayString<CAP>
     ///
     /// static ARRAY: ArrayString<1024> = ArrayString::new_const();
     /// ```
-    pub const fn new_const() -> ArrayString<CAP> {
-        assert_capacity_limit_const!(CAP);
-        ArrayString { xs: MakeMaybeUninit::ARRAY, len: 0 }
+    pub const fn new_const() -> ArrayString<CAP, L> {
+        let () = CapacityLimit::<CAP, L>::CHECK;
+        ArrayString { xs: MakeMaybeUninit::ARRAY, len: len_from_usize(0) }
+    }
+
+    /// Create a new `ArrayString` from a `str` (const fn).
+    ///
+    /// Capacity is inferred from the type parameter. The [`array_string!`]
+    /// macro can be used to infer it from the string instead.
+    ///
+    /// **Panics** if the backing array is not large enough to fit the string;
+    /// in a const context, this is a compile time error.
+    ///
+    /// [`array_string!`]: crate::array_string
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// const NAME: ArrayString<8> = ArrayString::from_str_const("name");
+    /// assert_eq!(&NAME[..], "name");
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use arrayvec::ArrayString;
+    ///
+    /// const NAME: ArrayString<2> = ArrayString::from_str_const("name");
+    /// ```
+    pub const fn from_str_const(s: &str) -> ArrayString<CAP, L> {
+        let () = CapacityLimit::<CAP, L>::CHECK;
+        let bytes = s.as_bytes();
+        let len = bytes.len();
+        assert!(len <= CAP, "ArrayString::from_str_const: string does not fit");
+        let mut xs = MakeMaybeUninit::ARRAY;
+        let mut i = 0;
+        while i < len {
+            xs[i] = MaybeUninit::new(bytes[i]);
+            i += 1;
+        }
+        ArrayString { xs, len: len_from_usize(len) }
     }
 
     /// Return the length of the string.
     #[inline]
-    pub const fn len(&self) -> usize { self.len as usize }
+    pub const fn len(&self) -> usize { len_to_usize(self.len) }
 
     /// Returns whether the string is empty.
     #[inline]
@@ -111,25 +158,125 @@ impl<const CAP: usize> ArrayString<CAP>
         Ok(arraystr)
     }
 
-    /// Create a new `ArrayString` from a byte string literal.
+    /// Create a new `ArrayString` from the characters of `iter`, without
+    /// panicking if they do not fit.
     ///
-    /// **Errors** if the byte string literal is not valid UTF-8.
+    /// Returns an error if the characters do not fit. The error holds the
+    /// string filled so far, the first character that did not fit, and the
+    /// rest of the iterator.
     ///
     /// ```
     /// use arrayvec::ArrayString;
     ///
-    /// let string = ArrayString::from_byte_string(b"hello world").unwrap();
+    /// let string = ArrayString::<4>::try_from_chars("abc".chars()).unwrap();
+    /// assert_eq!(&string[..], "abc");
+    ///
+    /// let (string, overflow, rest) = ArrayString::<4>::try_from_chars("abcdé".chars()).unwrap_err();
+    /// assert_eq!(&string[..], "abcd");
+    /// assert_eq!(overflow, 'é');
+    /// assert_eq!(rest.count(), 0);
     /// ```
-    pub fn from_byte_string(b: &[u8; CAP]) -> Result<Self, Utf8Error> {
-        let len = str::from_utf8(b)?.len();
-        debug_assert_eq!(len, CAP);
-        let mut vec = Self::new();
-        unsafe {
-            (b as *const [u8; CAP] as *const [MaybeUninit<u8>; CAP])
-                .copy_to_nonoverlapping(&mut vec.xs as *mut [MaybeUninit<u8>; CAP], 1);
-            vec.set_len(CAP);
+    pub fn try_from_chars<I>(iter: I) -> Result<Self, (Self, char, I::IntoIter)>
+        where I: IntoIterator<Item = char>
+    {
+        let mut string = Self::new();
+        let mut iter = iter.into_iter();
+        let mut overflow = None;
+        for c in iter.by_ref() {
+            if let Err(err) = string.try_push(c) {
+                overflow = Some(err.element());
+                break;
+            }
         }
-        Ok(vec)
+        match overflow {
+            Some(c) => Err((string, c, iter)),
+            None => Ok(string),
+        }
+    }
+
+    /// Create a new `ArrayString` by concatenating the string slices of
+    /// `iter`, without panicking if they do not fit.
+    ///
+    /// Returns an error if the string slices do not fit. The error holds the
+    /// string filled so far, the first string slice that did not fit (none of
+    /// which was appended), and the rest of the iterator.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let string = ArrayString::<8>::try_from_strs(vec!["ab", "cd"]).unwrap();
+    /// assert_eq!(&string[..], "abcd");
+    ///
+    /// let (string, overflow, mut rest) = ArrayString::<4>::try_from_strs(vec!["ab", "cde", "f"]).unwrap_err();
+    /// assert_eq!(&string[..], "ab");
+    /// assert_eq!(overflow, "cde");
+    /// assert_eq!(rest.next(), Some("f"));
+    /// ```
+    pub fn try_from_strs<'a, I>(iter: I) -> Result<Self, (Self, &'a str, I::IntoIter)>
+        where I: IntoIterator<Item = &'a str>
+    {
+        let mut string = Self::new();
+        let mut iter = iter.into_iter();
+        let mut overflow = None;
+        for s in iter.by_ref() {
+            if let Err(err) = string.try_push_str(s) {
+                overflow = Some(err.element());
+                break;
+            }
+        }
+        match overflow {
+            Some(s) => Err((string, s, iter)),
+            None => Ok(string),
+        }
+    }
+
+    /// Create a new `ArrayString` from formatted output.
+    ///
+    /// ***Panics*** if the output does not fit. See [`array_format!`] for a
+    /// shorthand, and `try_from_fmt` for fallible version.
+    ///
+    /// [`array_format!`]: crate::array_format
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let string = ArrayString::<8>::from_fmt(format_args!("{}+{}", 1, 2));
+    /// assert_eq!(&string[..], "1+2");
+    /// ```
+    #[track_caller]
+    pub fn from_fmt(args: fmt::Arguments<'_>) -> Self {
+        match Self::try_from_fmt(args) {
+            Ok(string) => string,
+            Err(err) if err.is_fmt_error() => {
+                panic!("ArrayString: a formatting trait implementation returned an error")
+            }
+            Err(err) => panic!("ArrayString: formatted output needs {} bytes, but the capacity is {}",
+                               err.required(), err.available()),
+        }
+    }
+
+    /// Create a new `ArrayString` from formatted output.
+    ///
+    /// Returns an error if the output does not fit, with the length in bytes
+    /// that the output needs and the capacity `CAP`. See
+    /// [`try_array_format!`] for a shorthand.
+    ///
+    /// [`try_array_format!`]: crate::try_array_format
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let string = ArrayString::<8>::try_from_fmt(format_args!("{}+{}", 1, 2)).unwrap();
+    /// assert_eq!(&string[..], "1+2");
+    ///
+    /// let err = ArrayString::<8>::try_from_fmt(format_args!("{:?}", "too long")).unwrap_err();
+    /// assert_eq!(err.required(), 10);
+    /// assert_eq!(err.available(), 8);
+    /// ```
+    pub fn try_from_fmt(args: fmt::Arguments<'_>) -> Result<Self, FormatError> {
+        let mut string = Self::new();
+        string.try_write_fmt(args)?;
+        Ok(string)
     }
 
     /// Create a new `ArrayString` value fully filled with ASCII NULL characters (`\0`). Useful
@@ -143,13 +290,13 @@ impl<const CAP: usize> ArrayString<CAP>
     /// ```
     #[inline]
     pub fn zero_filled() -> Self {
-        assert_capacity_limit!(CAP);
-        // SAFETY: `assert_capacity_limit` asserts that `len` won't overflow and
+        let () = CapacityLimit::<CAP, L>::CHECK;
+        // SAFETY: `CapacityLimit` checks that `len` won't overflow and
         // `zeroed` fully fills the array with nulls.
         unsafe {
             ArrayString {
                 xs: MaybeUninit::zeroed().assume_init(),
-                len: CAP as _
+                len: len_from_usize(CAP),
             }
         }
     }
@@ -257,8 +404,10 @@ impl<const CAP: usize> ArrayString<CAP>
     /// assert_eq!(&string[..], "ad");
     /// ```
     #[track_caller]
-    pub fn push_str(&mut self, s: &str) {
-        self.try_push_str(s).unwrap()
+    pub const fn push_str(&mut self, s: &str) {
+        if self.try_push_str(s).is_err() {
+            panic!("ArrayString::push_str: insufficient capacity");
+        }
     }
 
     /// Adds the given string slice to the end of the string.
@@ -281,7 +430,7 @@ impl<const CAP: usize> ArrayString<CAP>
     /// assert_eq!(overflow1.unwrap_err().element(), "bc");
     /// assert_eq!(overflow2.unwrap_err().element(), "ef");
     /// ```
-    pub fn try_push_str<'a>(&mut self, s: &'a str) -> Result<(), CapacityError<&'a str>> {
+    pub const fn try_push_str<'a>(&mut self, s: &'a str) -> Result<(), CapacityError<&'a str>> {
         if s.len() > self.capacity() - self.len() {
             return Err(CapacityError::new(s));
         }
@@ -295,6 +444,75 @@ impl<const CAP: usize> ArrayString<CAP>
         Ok(())
     }
 
+    /// Adds as much of the given string slice to the end of the string as
+    /// fits, and returns the rest.
+    ///
+    /// The string slice is split at a char boundary, so fewer bytes than the
+    /// remaining capacity may be added.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut string = ArrayString::<4>::new();
+    ///
+    /// assert_eq!(string.push_str_partial("ab"), "");
+    /// assert_eq!(string.push_str_partial("cé"), "é");
+    /// assert_eq!(&string[..], "abc");
+    /// ```
+    pub fn push_str_partial<'a>(&mut self, s: &'a str) -> &'a str {
+        let mut n = cmp::min(self.remaining_capacity(), s.len());
+        while !s.is_char_boundary(n) {
+            n -= 1;
+        }
+        let (head, rest) = s.split_at(n);
+        unsafe {
+            let dst = self.as_mut_ptr().add(self.len());
+            ptr::copy_nonoverlapping(head.as_ptr(), dst, n);
+            let newl = self.len() + n;
+            self.set_len(newl);
+        }
+        rest
+    }
+
+    /// Writes formatted output to the end of the string, or nothing at all
+    /// if it does not fit.
+    ///
+    /// If the output does not fit, or a formatting trait implementation
+    /// returns an error, the string is restored to its previous length and
+    /// an error is returned with the length in bytes that the output needs
+    /// and the remaining capacity. The [`try_push_fmt!`] macro can be used
+    /// instead of `format_args!`.
+    ///
+    /// [`try_push_fmt!`]: crate::try_push_fmt
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut string = ArrayString::<8>::new();
+    ///
+    /// string.try_write_fmt(format_args!("{}-{}", 1, 2)).unwrap();
+    /// let err = string.try_write_fmt(format_args!(" {:?}", "long")).unwrap_err();
+    /// assert_eq!((err.required(), err.available()), (7, 5));
+    /// assert_eq!(&string[..], "1-2");
+    /// ```
+    pub fn try_write_fmt(&mut self, args: fmt::Arguments<'_>) -> Result<(), FormatError> {
+        let len = self.len();
+        let available = self.remaining_capacity();
+        let mut writer = AtomicWriter { string: self, needed: 0, overflow: false };
+        let result = fmt::write(&mut writer, args);
+        let (needed, overflow) = (writer.needed, writer.overflow);
+        let error = match result {
+            Err(_) => FormatError::from_fmt_error(needed, available),
+            Ok(()) if overflow => FormatError::new(needed, available),
+            Ok(()) => return Ok(()),
+        };
+        // the length before the write is a char boundary
+        unsafe {
+            self.set_len(len);
+        }
+        Err(error)
+    }
+
     /// Removes the last character from the string and returns it.
     ///
     /// Returns `None` if this `ArrayString` is empty.
@@ -401,122 +619,313 @@ impl<const CAP: usize> ArrayString<CAP>
     ///
     /// This method uses *debug assertions* to check the validity of `length`
     /// and may use other debug assertions.
-    pub unsafe fn set_len(&mut self, length: usize) {
-        // type invariant that capacity always fits in LenUint
+    pub const unsafe fn set_len(&mut self, length: usize) {
+        // type invariant that capacity always fits in L
         debug_assert!(length <= self.capacity());
-        self.len = length as LenUint;
+        self.len = len_from_usize(length);
+    }
+
+    /// Copy the string into a new `ArrayString` with capacity `N`.
+    ///
+    /// Return an `Ok` value with the new string if it fits, return an `Err`
+    /// with self otherwise.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let string = ArrayString::<16>::from("foo").unwrap();
+    /// let small = string.into_capacity::<3>().unwrap();
+    /// assert_eq!(&small[..], "foo");
+    /// assert!(small.into_capacity::<2>().is_err());
+    /// ```
+    pub fn into_capacity<const N: usize>(self) -> Result<ArrayString<N, L>, Self> {
+        if self.len() > N {
+            Err(self)
+        } else {
+            unsafe { Ok(self.into_capacity_unchecked()) }
+        }
+    }
+
+    /// Copy the string into a new `ArrayString` with capacity `N`, which must
+    /// not be smaller than `CAP`.
+    ///
+    /// Using a capacity `N` that is smaller than `CAP` is a compile time error.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let string = ArrayString::<3>::from("foo").unwrap();
+    /// let wide = string.widen::<8>();
+    /// assert_eq!(&wide[..], "foo");
+    /// assert_eq!(wide.capacity(), 8);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use arrayvec::ArrayString;
+    ///
+    /// let string = ArrayString::<3>::from("foo").unwrap();
+    /// let narrow = string.widen::<2>();
+    /// ```
+    pub fn widen<const N: usize>(self) -> ArrayString<N, L> {
+        let () = CapacityFits::<CAP, N>::FITS;
+        unsafe { self.into_capacity_unchecked() }
+    }
+
+    /// Safety: the length must not be greater than `N`.
+    unsafe fn into_capacity_unchecked<const N: usize>(self) -> ArrayString<N, L> {
+        debug_assert!(self.len() <= N);
+        let mut string = ArrayString::new();
+        ptr::copy_nonoverlapping(self.as_ptr(), string.as_mut_ptr(), self.len());
+        string.set_len(self.len());
+        string
+    }
+
+    /// Save the current length of the string, and return a guard that
+    /// truncates the string back to it when dropped, unless it is committed.
+    ///
+    /// The guard dereferences to the string, so it can be used to add text.
+    /// Rolling back only removes text past the saved length.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut string = ArrayString::<8>::from("a").unwrap();
+    ///
+    /// let mut checkpoint = string.checkpoint();
+    /// checkpoint.push_str("bc");
+    /// drop(checkpoint);
+    /// assert_eq!(&string[..], "a");
+    ///
+    /// let mut checkpoint = string.checkpoint();
+    /// checkpoint.push_str("bc");
+    /// checkpoint.commit();
+    /// assert_eq!(&string[..], "abc");
+    /// ```
+    pub fn checkpoint(&mut self) -> StringCheckpoint<'_, CAP, L> {
+        let len = self.len();
+        StringCheckpoint { string: self, len }
+    }
+
+    /// Call `f` with the string, and roll back the text it added if it
+    /// returns an error or panics.
+    ///
+    /// See [`ArrayString::checkpoint`] for details.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut string = ArrayString::<8>::new();
+    /// let res = string.transaction(|s| {
+    ///     s.try_push_str("key=")?;
+    ///     s.try_push_str("long value")
+    /// });
+    /// assert!(res.is_err());
+    /// assert_eq!(&string[..], "");
+    /// ```
+    pub fn transaction<R, E, F>(&mut self, f: F) -> Result<R, E>
+        where F: FnOnce(&mut Self) -> Result<R, E>
+    {
+        let mut checkpoint = self.checkpoint();
+        let value = f(&mut checkpoint)?;
+        checkpoint.commit();
+        Ok(value)
     }
 
     /// Return a string slice of the whole `ArrayString`.
-    pub fn as_str(&self) -> &str {
-        self
+    pub const fn as_str(&self) -> &str {
+        unsafe {
+            let sl = slice::from_raw_parts(self.as_ptr(), self.len());
+            str::from_utf8_unchecked(sl)
+        }
     }
 
     /// Return a mutable string slice of the whole `ArrayString`.
-    pub fn as_mut_str(&mut self) -> &mut str {
-        self
+    pub const fn as_mut_str(&mut self) -> &mut str {
+        unsafe {
+            let len = self.len();
+            let sl = slice::from_raw_parts_mut(self.as_mut_ptr(), len);
+            str::from_utf8_unchecked_mut(sl)
+        }
     }
 
     /// Return a raw pointer to the string's buffer.
-    pub fn as_ptr(&self) -> *const u8 {
+    pub const fn as_ptr(&self) -> *const u8 {
         self.xs.as_ptr() as *const u8
     }
 
     /// Return a raw mutable pointer to the string's buffer.
-    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+    pub const fn as_mut_ptr(&mut self) -> *mut u8 {
         self.xs.as_mut_ptr() as *mut u8
     }
 }
 
-impl<const CAP: usize> Deref for ArrayString<CAP>
+impl<const CAP: usize> ArrayString<CAP>
+{
+    /// Create a new `ArrayString` from a byte string literal.
+    ///
+    /// **Errors** if the byte string literal is not valid UTF-8.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let string = ArrayString::from_byte_string(b"hello world").unwrap();
+    /// ```
+    pub fn from_byte_string(b: &[u8; CAP]) -> Result<Self, Utf8Error> {
+        let () = CapacityLimit::<CAP, LenUint>::CHECK;
+        let len = str::from_utf8(b)?.len();
+        debug_assert_eq!(len, CAP);
+        let mut vec = Self::new();
+        unsafe {
+            (b as *const [u8; CAP] as *const [MaybeUninit<u8>; CAP])
+                .copy_to_nonoverlapping(&mut vec.xs as *mut [MaybeUninit<u8>; CAP], 1);
+            vec.set_len(CAP);
+        }
+        Ok(vec)
+    }
+}
+
+/// A guard that rolls an `ArrayString` back to a saved length when dropped.
+///
+/// See [`ArrayString::checkpoint`] for details.
+pub struct StringCheckpoint<'a, const CAP: usize, L: LenType = LenUint> {
+    string: &'a mut ArrayString<CAP, L>,
+    len: usize,
+}
+
+impl<'a, const CAP: usize, L: LenType> StringCheckpoint<'a, CAP, L> {
+    /// Return the saved length.
+    pub fn saved_len(&self) -> usize {
+        self.len
+    }
+
+    /// Keep the changes made since the checkpoint.
+    pub fn commit(self) {
+        mem::forget(self);
+    }
+
+    /// Roll back the changes made since the checkpoint, now.
+    pub fn rollback(self) {}
+}
+
+impl<'a, const CAP: usize, L: LenType> Deref for StringCheckpoint<'a, CAP, L> {
+    type Target = ArrayString<CAP, L>;
+
+    fn deref(&self) -> &Self::Target {
+        self.string
+    }
+}
+
+impl<'a, const CAP: usize, L: LenType> DerefMut for StringCheckpoint<'a, CAP, L> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.string
+    }
+}
+
+impl<'a, const CAP: usize, L: LenType> Drop for StringCheckpoint<'a, CAP, L> {
+    fn drop(&mut self) {
+        if self.len < self.string.len() {
+            // The text before the saved length may have been replaced, so
+            // the saved length is not necessarily a char boundary anymore.
+            let mut len = self.len;
+            while !self.string.is_char_boundary(len) {
+                len -= 1;
+            }
+            self.string.truncate(len);
+        }
+    }
+}
+
+impl<'a, const CAP: usize, L: LenType> fmt::Debug for StringCheckpoint<'a, CAP, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StringCheckpoint")
+            .field("string", &self.string)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<const CAP: usize, L: LenType> Deref for ArrayString<CAP, L>
 {
     type Target = str;
     #[inline]
     fn deref(&self) -> &str {
-        unsafe {
-            let sl = slice::from_raw_parts(self.as_ptr(), self.len());
-            str::from_utf8_unchecked(sl)
-        }
+        self.as_str()
     }
 }
 
-impl<const CAP: usize> DerefMut for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> DerefMut for ArrayString<CAP, L>
 {
     #[inline]
     fn deref_mut(&mut self) -> &mut str {
-        unsafe {
-            let len = self.len();
-            let sl = slice::from_raw_parts_mut(self.as_mut_ptr(), len);
-            str::from_utf8_unchecked_mut(sl)
-        }
+        self.as_mut_str()
     }
 }
 
-impl<const CAP: usize> PartialEq for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> PartialEq for ArrayString<CAP, L>
 {
     fn eq(&self, rhs: &Self) -> bool {
         **self == **rhs
     }
 }
 
-impl<const CAP: usize> PartialEq<str> for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> PartialEq<str> for ArrayString<CAP, L>
 {
     fn eq(&self, rhs: &str) -> bool {
         &**self == rhs
     }
 }
 
-impl<const CAP: usize> PartialEq<ArrayString<CAP>> for str
+impl<const CAP: usize, L: LenType> PartialEq<ArrayString<CAP, L>> for str
 {
-    fn eq(&self, rhs: &ArrayString<CAP>) -> bool {
+    fn eq(&self, rhs: &ArrayString<CAP, L>) -> bool {
         self == &**rhs
     }
 }
 
-impl<const CAP: usize> Eq for ArrayString<CAP> 
+impl<const CAP: usize, L: LenType> Eq for ArrayString<CAP, L> 
 { }
 
-impl<const CAP: usize> Hash for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> Hash for ArrayString<CAP, L>
 {
     fn hash<H: Hasher>(&self, h: &mut H) {
         (**self).hash(h)
     }
 }
 
-impl<const CAP: usize> Borrow<str> for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> Borrow<str> for ArrayString<CAP, L>
 {
     fn borrow(&self) -> &str { self }
 }
 
-impl<const CAP: usize> BorrowMut<str> for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> BorrowMut<str> for ArrayString<CAP, L>
 {
     fn borrow_mut(&mut self) -> &mut str { self }
 }
 
-impl<const CAP: usize> AsRef<str> for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> AsRef<str> for ArrayString<CAP, L>
 {
     fn as_ref(&self) -> &str { self }
 }
 
-impl<const CAP: usize> fmt::Debug for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> fmt::Debug for ArrayString<CAP, L>
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
 }
 
 #[cfg(feature="std")]
-impl<const CAP: usize> AsRef<Path> for ArrayString<CAP> {
+impl<const CAP: usize, L: LenType> AsRef<Path> for ArrayString<CAP, L> {
     fn as_ref(&self) -> &Path {
         self.as_str().as_ref()
     }
 }
 
-impl<const CAP: usize> fmt::Display for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> fmt::Display for ArrayString<CAP, L>
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
 }
 
 /// `Write` appends written data to the end of the string.
-impl<const CAP: usize> fmt::Write for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> fmt::Write for ArrayString<CAP, L>
 {
     fn write_char(&mut self, c: char) -> fmt::Result {
         self.try_push(c).map_err(|_| fmt::Error)
@@ -527,9 +936,219 @@ impl<const CAP: usize> fmt::Write for ArrayString<CAP>
     }
 }
 
-impl<const CAP: usize> Clone for ArrayString<CAP>
+/// Writer for `try_write_fmt`: appends to the string until the first piece
+/// that does not fit, and counts the length of the whole output.
+struct AtomicWriter<'a, const CAP: usize, L: LenType> {
+    string: &'a mut ArrayString<CAP, L>,
+    needed: usize,
+    overflow: bool,
+}
+
+impl<const CAP: usize, L: LenType> fmt::Write for AtomicWriter<'_, CAP, L> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.needed += s.len();
+        if !self.overflow && self.string.try_push_str(s).is_err() {
+            self.overflow = true;
+        }
+        Ok(())
+    }
+}
+
+/// A `fmt::Write` adapter that appends to an `ArrayString`, and cuts the
+/// output to fit, ending it with a suffix when it is truncated.
+///
+/// If the output fits, it is written in full. Otherwise, it is cut at a char
+/// boundary so that the suffix fits after it, and the suffix (by default
+/// `"…"`) is appended; anything written after that is ignored. Text that was
+/// in the string before the writer was created is never removed, so if there
+/// is no room for the suffix after it, the suffix itself is cut.
+///
+/// Writes never return an error.
+///
+/// ```
+/// use std::fmt::Write;
+/// use arrayvec::{ArrayString, TruncatingWriter};
+///
+/// let mut string = ArrayString::<10>::new();
+/// let mut writer = TruncatingWriter::new(&mut string);
+/// write!(writer, "{}", "fits").unwrap();
+/// assert!(!writer.is_truncated());
+/// assert_eq!(&string[..], "fits");
+///
+/// let mut writer = TruncatingWriter::with_suffix(&mut string, "...");
+/// write!(writer, ", but {} does not", "this").unwrap();
+/// assert!(writer.is_truncated());
+/// assert_eq!(&string[..], "fits, b...");
+/// ```
+#[derive(Debug)]
+pub struct TruncatingWriter<'a, const CAP: usize, L: LenType = LenUint> {
+    string: &'a mut ArrayString<CAP, L>,
+    suffix: &'a str,
+    start: usize,
+    truncated: bool,
+}
+
+impl<'a, const CAP: usize, L: LenType> TruncatingWriter<'a, CAP, L> {
+    /// Create a writer that appends to `string`, using `"…"` as the suffix.
+    pub fn new(string: &'a mut ArrayString<CAP, L>) -> Self {
+        Self::with_suffix(string, "…")
+    }
+
+    /// Create a writer that appends to `string`, using `suffix` as the
+    /// suffix.
+    pub fn with_suffix(string: &'a mut ArrayString<CAP, L>, suffix: &'a str) -> Self {
+        let start = string.len();
+        TruncatingWriter { string, suffix, start, truncated: false }
+    }
+
+    /// Return true if the output was truncated.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+/// Return the largest char boundary in `s` that is not greater than `index`.
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    let mut index = cmp::min(index, s.len());
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+impl<const CAP: usize, L: LenType> fmt::Write for TruncatingWriter<'_, CAP, L> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.truncated || self.string.try_push_str(s).is_ok() {
+            return Ok(());
+        }
+        self.truncated = true;
+
+        // Cut the output so that the suffix fits after it, if possible.
+        let end = cmp::max(CAP.saturating_sub(self.suffix.len()), self.start);
+        let len = self.string.len();
+        if len < end {
+            let head = &s[..floor_char_boundary(s, end - len)];
+            self.string.push_str(head);
+        } else {
+            let cut = floor_char_boundary(self.string, end);
+            self.string.truncate(cut);
+        }
+        self.string.push_str_partial(self.suffix);
+        Ok(())
+    }
+}
+
+/// Create an `ArrayString` from a string literal, at compile time.
+///
+/// With one argument, the capacity is the length of the string in bytes.
+/// With two arguments, the capacity is given first; a string that does not
+/// fit in it is a compile time error. The macro can be used in `const` and
+/// `static` items. It is built on [`ArrayString::from_str_const`].
+///
+/// ```
+/// use arrayvec::{array_string, ArrayString};
+///
+/// static NAME: ArrayString<6> = array_string!("héllo");
+/// const PADDED: ArrayString<16> = array_string!(16, "héllo");
+///
+/// assert_eq!(&NAME[..], "héllo");
+/// assert_eq!(&NAME[..], &PADDED[..]);
+/// assert_eq!(PADDED.capacity(), 16);
+/// ```
+///
+/// ```compile_fail
+/// use arrayvec::array_string;
+///
+/// let name = array_string!(4, "héllo");
+/// ```
+#[macro_export]
+macro_rules! array_string {
+    ($s:expr) => {{
+        const __ARRAYVEC_STR: &str = $s;
+        const __ARRAYVEC_STRING: $crate::ArrayString<{ __ARRAYVEC_STR.len() }> =
+            $crate::ArrayString::from_str_const(__ARRAYVEC_STR);
+        __ARRAYVEC_STRING
+    }};
+    ($cap:expr, $s:expr) => {{
+        const __ARRAYVEC_STRING: $crate::ArrayString<{ $cap }> =
+            $crate::ArrayString::from_str_const($s);
+        __ARRAYVEC_STRING
+    }};
+}
+
+/// Create an `ArrayString` with capacity `CAP` from formatted text, like
+/// `format!`.
+///
+/// The capacity is given first, followed by a semicolon and the arguments of
+/// `format!`. This is a shorthand for [`ArrayString::from_fmt`].
+///
+/// ***Panics*** if the output does not fit. See [`try_array_format!`] for
+/// fallible version.
+///
+/// [`try_array_format!`]: crate::try_array_format
+///
+/// ```
+/// use arrayvec::array_format;
+///
+/// let string = array_format!(16; "{}-{:03}", "id", 7);
+/// assert_eq!(&string[..], "id-007");
+/// assert_eq!(string.capacity(), 16);
+/// ```
+#[macro_export]
+macro_rules! array_format {
+    ($cap:expr; $($arg:tt)*) => {
+        $crate::ArrayString::<{ $cap }>::from_fmt(format_args!($($arg)*))
+    };
+}
+
+/// Create an `ArrayString` with capacity `CAP` from formatted text, like
+/// `format!`, or return an error if it does not fit.
+///
+/// The capacity is given first, followed by a semicolon and the arguments of
+/// `format!`. This is a shorthand for [`ArrayString::try_from_fmt`]: the
+/// error tells the length in bytes that the output needs and the capacity.
+///
+/// ```
+/// use arrayvec::try_array_format;
+///
+/// let string = try_array_format!(16; "{}-{:03}", "id", 7).unwrap();
+/// assert_eq!(&string[..], "id-007");
+///
+/// let err = try_array_format!(4; "{}-{:03}", "id", 7).unwrap_err();
+/// assert_eq!((err.required(), err.available()), (6, 4));
+/// ```
+#[macro_export]
+macro_rules! try_array_format {
+    ($cap:expr; $($arg:tt)*) => {
+        $crate::ArrayString::<{ $cap }>::try_from_fmt(format_args!($($arg)*))
+    };
+}
+
+/// Write formatted text to the end of an `ArrayString`, or nothing at all if
+/// it does not fit.
+///
+/// This is a shorthand for [`ArrayString::try_write_fmt`] with
+/// `format_args!`, and evaluates to its `Result`.
+///
+/// ```
+/// use arrayvec::{ArrayString, try_push_fmt};
+///
+/// let mut string = ArrayString::<12>::new();
+/// try_push_fmt!(string, "x={} ", 1).unwrap();
+/// assert!(try_push_fmt!(string, "y={:?}", "longer").is_err());
+/// try_push_fmt!(string, "y={}", 2).unwrap();
+/// assert_eq!(&string[..], "x=1 y=2");
+/// ```
+#[macro_export]
+macro_rules! try_push_fmt {
+    ($string:expr, $($arg:tt)*) => {
+        $string.try_write_fmt(format_args!($($arg)*))
+    };
+}
+
+impl<const CAP: usize, L: LenType> Clone for ArrayString<CAP, L>
 {
-    fn clone(&self) -> ArrayString<CAP> {
+    fn clone(&self) -> ArrayString<CAP, L> {
         *self
     }
     fn clone_from(&mut self, rhs: &Self) {
@@ -539,7 +1158,7 @@ impl<const CAP: usize> Clone for ArrayString<CAP>
     }
 }
 
-impl<const CAP: usize> PartialOrd for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> PartialOrd for ArrayString<CAP, L>
 {
     fn partial_cmp(&self, rhs: &Self) -> Option<cmp::Ordering> {
         (**self).partial_cmp(&**rhs)
@@ -550,7 +1169,7 @@ impl<const CAP: usize> PartialOrd for ArrayString<CAP>
     fn ge(&self, rhs: &Self) -> bool { **self >= **rhs }
 }
 
-impl<const CAP: usize> PartialOrd<str> for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> PartialOrd<str> for ArrayString<CAP, L>
 {
     fn partial_cmp(&self, rhs: &str) -> Option<cmp::Ordering> {
         (**self).partial_cmp(rhs)
@@ -561,25 +1180,25 @@ impl<const CAP: usize> PartialOrd<str> for ArrayString<CAP>
     fn ge(&self, rhs: &str) -> bool { &**self >= rhs }
 }
 
-impl<const CAP: usize> PartialOrd<ArrayString<CAP>> for str
+impl<const CAP: usize, L: LenType> PartialOrd<ArrayString<CAP, L>> for str
 {
-    fn partial_cmp(&self, rhs: &ArrayString<CAP>) -> Option<cmp::Ordering> {
+    fn partial_cmp(&self, rhs: &ArrayString<CAP, L>) -> Option<cmp::Ordering> {
         self.partial_cmp(&**rhs)
     }
-    fn lt(&self, rhs: &ArrayString<CAP>) -> bool { self < &**rhs }
-    fn le(&self, rhs: &ArrayString<CAP>) -> bool { self <= &**rhs }
-    fn gt(&self, rhs: &ArrayString<CAP>) -> bool { self > &**rhs }
-    fn ge(&self, rhs: &ArrayString<CAP>) -> bool { self >= &**rhs }
+    fn lt(&self, rhs: &ArrayString<CAP, L>) -> bool { self < &**rhs }
+    fn le(&self, rhs: &ArrayString<CAP, L>) -> bool { self <= &**rhs }
+    fn gt(&self, rhs: &ArrayString<CAP, L>) -> bool { self > &**rhs }
+    fn ge(&self, rhs: &ArrayString<CAP, L>) -> bool { self >= &**rhs }
 }
 
-impl<const CAP: usize> Ord for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> Ord for ArrayString<CAP, L>
 {
     fn cmp(&self, rhs: &Self) -> cmp::Ordering {
         (**self).cmp(&**rhs)
     }
 }
 
-impl<const CAP: usize> FromStr for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> FromStr for ArrayString<CAP, L>
 {
     type Err = CapacityError;
 
@@ -590,7 +1209,7 @@ impl<const CAP: usize> FromStr for ArrayString<CAP>
 
 #[cfg(feature="serde")]
 /// Requires crate feature `"serde"`
-impl<const CAP: usize> Serialize for ArrayString<CAP>
+impl<const CAP: usize, L: LenType> Serialize for ArrayString<CAP, L>
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer
@@ -601,7 +1220,7 @@ impl<const CAP: usize> Serialize for ArrayString<CAP>
 
 #[cfg(feature="serde")]
 /// Requires crate feature `"serde"`
-impl<'de, const CAP: usize> Deserialize<'de> for ArrayString<CAP> 
+impl<'de, const CAP: usize, L: LenType> Deserialize<'de> for ArrayString<CAP, L> 
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: Deserializer<'de>
@@ -609,10 +1228,10 @@ impl<'de, const CAP: usize> Deserialize<'de> for ArrayString<CAP>
         use serde::de::{self, Visitor};
         use std::marker::PhantomData;
 
-        struct ArrayStringVisitor<const CAP: usize>(PhantomData<[u8; CAP]>);
+        struct ArrayStringVisitor<const CAP: usize, L: LenType>(PhantomData<([u8; CAP], L)>);
 
-        impl<'de, const CAP: usize> Visitor<'de> for ArrayStringVisitor<CAP> {
-            type Value = ArrayString<CAP>;
+        impl<'de, const CAP: usize, L: LenType> Visitor<'de> for ArrayStringVisitor<CAP, L> {
+            type Value = ArrayString<CAP, L>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 write!(formatter, "a string no more than {} bytes long", CAP)
@@ -639,7 +1258,7 @@ impl<'de, const CAP: usize> Deserialize<'de> for ArrayString<CAP>
 
 #[cfg(feature = "borsh")]
 /// Requires crate feature `"borsh"`
-impl<const CAP: usize> borsh::BorshSerialize for ArrayString<CAP> {
+impl<const CAP: usize, L: LenType> borsh::BorshSerialize for ArrayString<CAP, L> {
     fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
         <str as borsh::BorshSerialize>::serialize(&*self, writer)
     }
@@ -647,7 +1266,7 @@ impl<const CAP: usize> borsh::BorshSerialize for ArrayString<CAP> {
 
 #[cfg(feature = "borsh")]
 /// Requires crate feature `"borsh"`
-impl<const CAP: usize> borsh::BorshDeserialize for ArrayString<CAP> {
+impl<const CAP: usize, L: LenType> borsh::BorshDeserialize for ArrayString<CAP, L> {
     fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
         let len = <u32 as borsh::BorshDeserialize>::deserialize_reader(reader)? as usize;
         if len > CAP {
@@ -668,7 +1287,7 @@ impl<const CAP: usize> borsh::BorshDeserialize for ArrayString<CAP> {
     }
 }
 
-impl<'a, const CAP: usize> TryFrom<&'a str> for ArrayString<CAP>
+impl<'a, const CAP: usize, L: LenType> TryFrom<&'a str> for ArrayString<CAP, L>
 {
     type Error = CapacityError<&'a str>;
 
@@ -679,7 +1298,7 @@ impl<'a, const CAP: usize> TryFrom<&'a str> for ArrayString<CAP>
     }
 }
 
-impl<'a, const CAP: usize> TryFrom<fmt::Arguments<'a>> for ArrayString<CAP>
+impl<'a, const CAP: usize, L: LenType> TryFrom<fmt::Arguments<'a>> for ArrayString<CAP, L>
 {
     type Error = CapacityError<fmt::Error>;
 
@@ -706,7 +1325,7 @@ impl<'a, const CAP: usize> TryFrom<fmt::Arguments<'a>> for ArrayString<CAP>
 /// unsafe { string.set_len(string.capacity()) };
 /// assert_eq!(&*string, "\0\0\0\0\0\0");
 /// ```
-impl<const CAP: usize> zeroize::Zeroize for ArrayString<CAP> {
+impl<const CAP: usize, L: LenType> zeroize::Zeroize for ArrayString<CAP, L> {
     fn zeroize(&mut self) {
         // There are no elements to drop
         self.clear();
@@ -1,8 +1,9 @@
 
 use std::cmp;
+use std::convert::Infallible;
 use std::iter;
 use std::mem;
-use std::ops::{Bound, Deref, DerefMut, RangeBounds};
+use std::ops::{Deref, DerefMut, RangeBounds};
 use std::ptr;
 use std::slice;
 
@@ -21,32 +22,68 @@ use std::mem::MaybeUninit;
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
 
 use crate::LenUint;
+use crate::len_type::{LenType, len_from_usize, len_to_usize};
 use crate::errors::CapacityError;
-use crate::arrayvec_impl::ArrayVecImpl;
-use crate::utils::MakeMaybeUninit;
+use crate::utils::{ArrayPrefix, CapacityFits, CapacityLimit, MakeMaybeUninit, range_to_indices};
 
 /// A vector with a fixed capacity.
 ///
 /// The `ArrayVec` is a vector backed by a fixed size array. It keeps track of
-/// the number of initialized elements. The `ArrayVec<T, CAP>` is parameterized
-/// by `T` for the element type and `CAP` for the maximum capacity.
+/// the number of initialized elements. The `ArrayVec<T, CAP, L>` is parameterized
+/// by `T` for the element type, `CAP` for the maximum capacity and the optional `L` for the
+/// [`LenType`](crate::LenType) that stores the length.
 ///
-/// `CAP` is of type `usize` but is range limited to `u32::MAX` (or `u16::MAX` on 16-bit targets);
-/// attempting to create larger arrayvecs with larger capacity will panic.
+/// `CAP` is of type `usize` but is range limited to the maximum of `L`, which is `u32::MAX` by
+/// default (or `u16::MAX` on 16-bit targets); attempting to create larger arrayvecs with larger
+/// capacity is a compile time error, in every constructor.
+///
+/// ```compile_fail
+/// use arrayvec::ArrayVec;
+///
+/// let array = ArrayVec::<(), { u32::MAX as usize + 1 }>::new();
+/// ```
+///
+/// ```compile_fail
+/// use arrayvec::ArrayVec;
+///
+/// static ARRAY: ArrayVec<(), { u32::MAX as usize + 1 }> = ArrayVec::new_const();
+/// ```
 ///
 /// The vector is a contiguous value (storing the elements inline) that you can store directly on
 /// the stack if needed.
 ///
 /// It offers a simple API but also dereferences to a slice, so that the full slice API is
 /// available. The ArrayVec can be converted into a by value iterator.
+///
+/// The basic methods like `try_push`, `pop` and `as_slice` are `const fn`,
+/// as well as `push_const` for `Copy` elements, so an `ArrayVec` can be built
+/// at compile time. Since its destructor can not run
+/// in a const context, the vector must be returned rather than dropped.
+///
+/// ```
+/// use arrayvec::ArrayVec;
+///
+/// const fn powers_of_two() -> ArrayVec<u32, 8> {
+///     let mut array = ArrayVec::new_const();
+///     let mut power = 1;
+///     while power < 100 {
+///         array.push_const(power);
+///         power *= 2;
+///     }
+///     array
+/// }
+///
+/// static POWERS: ArrayVec<u32, 8> = powers_of_two();
+/// assert_eq!(&POWERS[..], &[1, 2, 4, 8, 16, 32, 64]);
+/// ```
 #[repr(C)]
-pub struct ArrayVec<T, const CAP: usize> {
-    len: LenUint,
+pub struct ArrayVec<T, const CAP: usize, L: LenType = LenUint> {
+    len: L,
     // the `len` first elements of the array are initialized
     xs: [MaybeUninit<T>; CAP],
 }
 
-impl<T, const CAP: usize> Drop for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> Drop for ArrayVec<T, CAP, L> {
     fn drop(&mut self) {
         self.clear();
 
@@ -61,7 +98,7 @@ macro_rules! panic_oob {
     }
 }
 
-impl<T, const CAP: usize> ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> ArrayVec<T, CAP, L> {
     /// Capacity
     const CAPACITY: usize = CAP;
 
@@ -79,11 +116,10 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     /// assert_eq!(array.capacity(), 16);
     /// ```
     #[inline]
-    #[track_caller]
-    pub fn new() -> ArrayVec<T, CAP> {
-        assert_capacity_limit!(CAP);
+    pub fn new() -> ArrayVec<T, CAP, L> {
+        let () = CapacityLimit::<CAP, L>::CHECK;
         unsafe {
-            ArrayVec { xs: MaybeUninit::uninit().assume_init(), len: 0 }
+            ArrayVec { xs: MaybeUninit::uninit().assume_init(), len: len_from_usize(0) }
         }
     }
 
@@ -96,9 +132,135 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     ///
     /// static ARRAY: ArrayVec<u8, 1024> = ArrayVec::new_const();
     /// ```
-    pub const fn new_const() -> ArrayVec<T, CAP> {
-        assert_capacity_limit_const!(CAP);
-        ArrayVec { xs: MakeMaybeUninit::ARRAY, len: 0 }
+    pub const fn new_const() -> ArrayVec<T, CAP, L> {
+        let () = CapacityLimit::<CAP, L>::CHECK;
+        ArrayVec { xs: MakeMaybeUninit::ARRAY, len: len_from_usize(0) }
+    }
+
+    /// Create a new `ArrayVec` from an array of `N` elements, which must
+    /// not be more than `CAP` (const fn).
+    ///
+    /// Unlike `From<[T; CAP]>`, the array can be shorter than the capacity;
+    /// see also the [`arrayvec!`] macro. Using an array with more than `CAP`
+    /// elements is a compile time error.
+    ///
+    /// [`arrayvec!`]: crate::arrayvec
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// static PRIMES: ArrayVec<u32, 8> = ArrayVec::from_array_prefix([2, 3, 5, 7]);
+    /// assert_eq!(&PRIMES[..], &[2, 3, 5, 7]);
+    /// assert_eq!(PRIMES.capacity(), 8);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let array = ArrayVec::<u32, 2>::from_array_prefix([2, 3, 5, 7]);
+    /// ```
+    pub const fn from_array_prefix<const N: usize>(array: [T; N]) -> ArrayVec<T, CAP, L> {
+        let () = CapacityLimit::<CAP, L>::CHECK;
+        let () = CapacityFits::<N, CAP>::FITS;
+        let prefix = ArrayPrefix::<T, N, CAP> { array: ManuallyDrop::new(array) };
+        unsafe {
+            ArrayVec { xs: ManuallyDrop::into_inner(prefix.xs), len: len_from_usize(N) }
+        }
+    }
+
+    /// Create a new `ArrayVec` from the elements of `iter`, without panicking
+    /// if there are too many of them.
+    ///
+    /// Returns an error if the iterator yields more than `CAP` elements. The
+    /// error holds the full vector, the first element that did not fit, and
+    /// the rest of the iterator.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let array = ArrayVec::<_, 4>::try_from_iter(0..3).unwrap();
+    /// assert_eq!(&array[..], &[0, 1, 2]);
+    ///
+    /// let (array, overflow, rest) = ArrayVec::<_, 2>::try_from_iter(0..5).unwrap_err();
+    /// assert_eq!(&array[..], &[0, 1]);
+    /// assert_eq!(overflow, 2);
+    /// assert_eq!(rest.collect::<Vec<_>>(), [3, 4]);
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, (Self, T, I::IntoIter)>
+        where I: IntoIterator<Item = T>
+    {
+        let mut array = Self::new();
+        let mut iter = iter.into_iter();
+        unsafe {
+            array.extend_from_iter::<_, false>(iter.by_ref().take(CAP));
+        }
+        match iter.next() {
+            Some(elt) => Err((array, elt, iter)),
+            None => Ok(array),
+        }
+    }
+
+    /// Create a new `ArrayVec` of length `len`, where each element is
+    /// produced by calling `f` with its index.
+    ///
+    /// ***Panics*** if `len` is greater than the capacity.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let array = ArrayVec::<_, 8>::from_fn(4, |i| i * i);
+    /// assert_eq!(&array[..], &[0, 1, 4, 9]);
+    /// ```
+    #[track_caller]
+    pub fn from_fn<F>(len: usize, mut f: F) -> Self
+        where F: FnMut(usize) -> T
+    {
+        match Self::try_from_fn(len, |i| Ok::<_, Infallible>(f(i))) {
+            Ok(array) => array,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Create a new `ArrayVec` of length `len`, where each element is
+    /// produced by calling `f` with its index, stopping at the first error.
+    ///
+    /// If `f` returns an error, the elements produced so far are dropped and
+    /// the error is returned.
+    ///
+    /// ***Panics*** if `len` is greater than the capacity.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let strs = ["1", "2", "3"];
+    /// let array = ArrayVec::<u8, 4>::try_from_fn(3, |i| strs[i].parse()).unwrap();
+    /// assert_eq!(&array[..], &[1, 2, 3]);
+    ///
+    /// let strs = ["1", "x", "3"];
+    /// assert!(ArrayVec::<u8, 4>::try_from_fn(3, |i| strs[i].parse()).is_err());
+    /// ```
+    #[track_caller]
+    pub fn try_from_fn<E, F>(len: usize, mut f: F) -> Result<Self, E>
+        where F: FnMut(usize) -> Result<T, E>
+    {
+        if len > CAP {
+            panic!("ArrayVec: length {} exceeds capacity {}", len, CAP);
+        }
+        let mut array = Self::new();
+        let ptr = array.as_mut_ptr();
+        {
+            // WritebackGuard updates array.len on drop (both success and
+            // error or panic), so that the elements built so far are dropped.
+            let mut guard = WritebackGuard { target: &mut array.len, len: 0 };
+            while guard.len < len {
+                let elt = f(guard.len)?;
+                unsafe {
+                    ptr.add(guard.len).write(elt);
+                }
+                guard.len += 1;
+            }
+        }
+        Ok(array)
     }
 
     /// Return the number of elements in the `ArrayVec`.
@@ -111,7 +273,7 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     /// assert_eq!(array.len(), 2);
     /// ```
     #[inline(always)]
-    pub const fn len(&self) -> usize { self.len as usize }
+    pub const fn len(&self) -> usize { len_to_usize(self.len) }
 
     /// Returns whether the `ArrayVec` is empty.
     ///
@@ -177,7 +339,38 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     /// ```
     #[track_caller]
     pub fn push(&mut self, element: T) {
-        ArrayVecImpl::push(self, element)
+        self.try_push(element).unwrap()
+    }
+
+    /// Push `element` to the end of the vector (const fn).
+    ///
+    /// Like [`push`](ArrayVec::push), but only for `Copy` elements, which
+    /// have nothing to drop when the vector is full.
+    ///
+    /// ***Panics*** if the vector is already full.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// const ARRAY: ArrayVec<i32, 3> = {
+    ///     let mut array = ArrayVec::new_const();
+    ///     array.push_const(1);
+    ///     array.push_const(2);
+    ///     array
+    /// };
+    /// assert_eq!(&ARRAY[..], &[1, 2]);
+    /// ```
+    #[track_caller]
+    pub const fn push_const(&mut self, element: T)
+        where T: Copy
+    {
+        if self.len() < CAP {
+            unsafe {
+                self.push_unchecked(element);
+            }
+        } else {
+            panic!("ArrayVec::push_const: insufficient capacity");
+        }
     }
 
     /// Push `element` to the end of the vector.
@@ -202,8 +395,15 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     ///
     /// assert!(overflow.is_err());
     /// ```
-    pub fn try_push(&mut self, element: T) -> Result<(), CapacityError<T>> {
-        ArrayVecImpl::try_push(self, element)
+    pub const fn try_push(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        if self.len() < CAP {
+            unsafe {
+                self.push_unchecked(element);
+            }
+            Ok(())
+        } else {
+            Err(CapacityError::new(element))
+        }
     }
 
     /// Push `element` to the end of the vector without checking the capacity.
@@ -227,8 +427,11 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     ///
     /// assert_eq!(&array[..], &[1, 2]);
     /// ```
-    pub unsafe fn push_unchecked(&mut self, element: T) {
-        ArrayVecImpl::push_unchecked(self, element)
+    pub const unsafe fn push_unchecked(&mut self, element: T) {
+        let len = self.len();
+        debug_assert!(len < CAP);
+        ptr::write(self.as_mut_ptr().add(len), element);
+        self.set_len(len + 1);
     }
 
     /// Shortens the vector, keeping the first `len` elements and dropping
@@ -247,12 +450,162 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     /// assert_eq!(&array[..], &[1, 2, 3]);
     /// ```
     pub fn truncate(&mut self, new_len: usize) {
-        ArrayVecImpl::truncate(self, new_len)
+        unsafe {
+            let len = self.len();
+            if new_len < len {
+                self.set_len(new_len);
+                let tail = slice::from_raw_parts_mut(self.as_mut_ptr().add(new_len), len - new_len);
+                ptr::drop_in_place(tail);
+            }
+        }
+    }
+
+    /// Shortens the vector, keeping the first `len` elements (const fn).
+    ///
+    /// Like [`truncate`](ArrayVec::truncate), but only for `Copy` elements,
+    /// which have nothing to drop.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// const ARRAY: ArrayVec<i32, 5> = {
+    ///     let mut array = ArrayVec::from_array_prefix([1, 2, 3, 4]);
+    ///     array.truncate_const(2);
+    ///     array
+    /// };
+    /// assert_eq!(&ARRAY[..], &[1, 2]);
+    /// ```
+    pub const fn truncate_const(&mut self, new_len: usize)
+        where T: Copy
+    {
+        if new_len < self.len() {
+            self.len = len_from_usize(new_len);
+        }
     }
 
     /// Remove all elements in the vector.
     pub fn clear(&mut self) {
-        ArrayVecImpl::clear(self)
+        self.truncate(0)
+    }
+
+    /// Resize the vector in place so that its length is `new_len`.
+    ///
+    /// If `new_len` is greater than the length, the vector is extended by
+    /// clones of `value`; otherwise the vector is truncated.
+    ///
+    /// ***Panics*** if `new_len` is greater than the capacity. See
+    /// `try_resize` for fallible version.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 5>::new();
+    /// array.push("a");
+    /// array.resize(3, "b");
+    /// assert_eq!(&array[..], &["a", "b", "b"]);
+    /// array.resize(1, "c");
+    /// assert_eq!(&array[..], &["a"]);
+    /// ```
+    #[track_caller]
+    pub fn resize(&mut self, new_len: usize, value: T)
+        where T: Clone
+    {
+        self.try_resize(new_len, value).unwrap()
+    }
+
+    /// Resize the vector in place so that its length is `new_len`.
+    ///
+    /// If `new_len` is greater than the length, the vector is extended by
+    /// clones of `value`; otherwise the vector is truncated.
+    ///
+    /// Returns an error with `value` if `new_len` is greater than the
+    /// capacity; in that case the vector is left unchanged.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 3>::new();
+    /// array.try_resize(2, 0).unwrap();
+    /// assert_eq!(&array[..], &[0, 0]);
+    ///
+    /// let err = array.try_resize(4, 1);
+    /// assert_eq!(err.unwrap_err().element(), 1);
+    /// assert_eq!(&array[..], &[0, 0]);
+    /// ```
+    pub fn try_resize(&mut self, new_len: usize, value: T) -> Result<(), CapacityError<T>>
+        where T: Clone
+    {
+        if new_len > CAP {
+            return Err(CapacityError::new(value));
+        }
+        let len = self.len();
+        if new_len <= len {
+            self.truncate(new_len);
+        } else {
+            // Clone for all new elements but the last, which gets `value`.
+            unsafe {
+                self.extend_from_iter::<_, false>(
+                    iter::repeat_with(|| value.clone()).take(new_len - len - 1));
+                self.push_unchecked(value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Resize the vector in place so that its length is `new_len`.
+    ///
+    /// If `new_len` is greater than the length, the vector is extended by
+    /// values returned from calling `f`; otherwise the vector is truncated.
+    ///
+    /// ***Panics*** if `new_len` is greater than the capacity. See
+    /// `try_resize_with` for fallible version.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 5>::new();
+    /// let mut counter = 0;
+    /// array.resize_with(4, || { counter += 1; counter });
+    /// assert_eq!(&array[..], &[1, 2, 3, 4]);
+    /// ```
+    #[track_caller]
+    pub fn resize_with<F>(&mut self, new_len: usize, f: F)
+        where F: FnMut() -> T
+    {
+        self.try_resize_with(new_len, f).unwrap()
+    }
+
+    /// Resize the vector in place so that its length is `new_len`.
+    ///
+    /// If `new_len` is greater than the length, the vector is extended by
+    /// values returned from calling `f`; otherwise the vector is truncated.
+    ///
+    /// Returns an error if `new_len` is greater than the capacity; in that
+    /// case the vector is left unchanged and `f` is not called.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<i32, 3>::new();
+    /// assert!(array.try_resize_with(4, Default::default).is_err());
+    /// array.try_resize_with(3, Default::default).unwrap();
+    /// assert_eq!(&array[..], &[0, 0, 0]);
+    /// ```
+    pub fn try_resize_with<F>(&mut self, new_len: usize, f: F) -> Result<(), CapacityError>
+        where F: FnMut() -> T
+    {
+        if new_len > CAP {
+            return Err(CapacityError::new(()));
+        }
+        let len = self.len();
+        if new_len <= len {
+            self.truncate(new_len);
+        } else {
+            unsafe {
+                self.extend_from_iter::<_, false>(iter::repeat_with(f).take(new_len - len));
+            }
+        }
+        Ok(())
     }
 
 
@@ -332,102 +685,306 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
         Ok(())
     }
 
-    /// Remove the last element in the vector and return it.
+    /// Insert a copy of all elements of `slice` at position `index`.
     ///
-    /// Return `Some(` *element* `)` if the vector is non-empty, else `None`.
+    /// Shift up all elements after `index`, once; the `index` must be less
+    /// than or equal to the length.
+    ///
+    /// Returns an error if the elements do not fit in the remaining
+    /// capacity; in that case the vector is left unchanged.
+    ///
+    /// ***Panics*** if `index` is out of bounds.
     ///
     /// ```
     /// use arrayvec::ArrayVec;
     ///
-    /// let mut array = ArrayVec::<_, 2>::new();
-    ///
-    /// array.push(1);
+    /// let mut array = ArrayVec::<_, 5>::new();
+    /// array.extend([1, 4].iter().copied());
     ///
-    /// assert_eq!(array.pop(), Some(1));
-    /// assert_eq!(array.pop(), None);
+    /// array.try_insert_slice(1, &[2, 3]).unwrap();
+    /// assert_eq!(&array[..], &[1, 2, 3, 4]);
+    /// assert!(array.try_insert_slice(0, &[5, 6]).is_err());
     /// ```
-    pub fn pop(&mut self) -> Option<T> {
-        ArrayVecImpl::pop(self)
+    pub fn try_insert_slice(&mut self, index: usize, slice: &[T]) -> Result<(), CapacityError>
+        where T: Copy,
+    {
+        let len = self.len();
+        if index > len {
+            panic_oob!("try_insert_slice", index, len)
+        }
+        let n = slice.len();
+        if self.remaining_capacity() < n {
+            return Err(CapacityError::new(()));
+        }
+
+        unsafe {
+            let p: *mut _ = self.get_unchecked_ptr(index);
+            ptr::copy(p, p.add(n), len - index);
+            ptr::copy_nonoverlapping(slice.as_ptr(), p, n);
+            self.set_len(len + n);
+        }
+        Ok(())
     }
 
-    /// Remove the element at `index` and swap the last element into its place.
+    /// Insert a clone of all elements of `slice` at position `index`.
     ///
-    /// This operation is O(1).
+    /// Shift up all elements after `index`, once; the `index` must be less
+    /// than or equal to the length. If cloning an element panics, the
+    /// elements cloned so far are kept.
     ///
-    /// Return the *element* if the index is in bounds, else panic.
+    /// Returns an error if the elements do not fit in the remaining
+    /// capacity; in that case the vector is left unchanged.
     ///
-    /// ***Panics*** if the `index` is out of bounds.
+    /// ***Panics*** if `index` is out of bounds.
     ///
     /// ```
     /// use arrayvec::ArrayVec;
     ///
-    /// let mut array = ArrayVec::from([1, 2, 3]);
-    ///
-    /// assert_eq!(array.swap_remove(0), 1);
-    /// assert_eq!(&array[..], &[3, 2]);
+    /// let mut array = ArrayVec::<String, 3>::new();
+    /// array.push("c".to_string());
     ///
-    /// assert_eq!(array.swap_remove(1), 2);
-    /// assert_eq!(&array[..], &[3]);
+    /// array.try_insert_slice_cloned(0, &["a".to_string(), "b".to_string()]).unwrap();
+    /// assert_eq!(&array[..], &["a", "b", "c"]);
     /// ```
-    pub fn swap_remove(&mut self, index: usize) -> T {
-        self.swap_pop(index)
-            .unwrap_or_else(|| {
-                panic_oob!("swap_remove", index, self.len())
-            })
+    pub fn try_insert_slice_cloned(&mut self, index: usize, slice: &[T])
+        -> Result<(), CapacityError>
+        where T: Clone,
+    {
+        let len = self.len();
+        if index > len {
+            panic_oob!("try_insert_slice_cloned", index, len)
+        }
+        if self.remaining_capacity() < slice.len() {
+            return Err(CapacityError::new(()));
+        }
+        unsafe {
+            self.insert_from_iter(index, slice.len(), slice.iter().cloned());
+        }
+        Ok(())
     }
 
-    /// Remove the element at `index` and swap the last element into its place.
+    /// Insert all elements of `iter` at position `index`.
     ///
-    /// This is a checked version of `.swap_remove`.  
-    /// This operation is O(1).
+    /// Shift up all elements after `index`, once; the `index` must be less
+    /// than or equal to the length.
     ///
-    /// Return `Some(` *element* `)` if the index is in bounds, else `None`.
+    /// ***Panics*** if the elements do not fit in the remaining capacity or
+    /// if `index` is out of bounds. See `try_insert_iter` for fallible version.
     ///
     /// ```
     /// use arrayvec::ArrayVec;
     ///
-    /// let mut array = ArrayVec::from([1, 2, 3]);
-    ///
-    /// assert_eq!(array.swap_pop(0), Some(1));
-    /// assert_eq!(&array[..], &[3, 2]);
+    /// let mut array = ArrayVec::<_, 5>::new();
+    /// array.extend([0, 4].iter().copied());
     ///
-    /// assert_eq!(array.swap_pop(10), None);
+    /// array.insert_iter(1, 1..4);
+    /// assert_eq!(&array[..], &[0, 1, 2, 3, 4]);
     /// ```
-    pub fn swap_pop(&mut self, index: usize) -> Option<T> {
-        let len = self.len();
-        if index >= len {
-            return None;
-        }
-        self.swap(index, len - 1);
-        self.pop()
+    #[track_caller]
+    pub fn insert_iter<I>(&mut self, index: usize, iter: I)
+        where I: IntoIterator<Item = T>,
+              I::IntoIter: ExactSizeIterator,
+    {
+        self.try_insert_iter(index, iter).unwrap()
     }
 
-    /// Remove the element at `index` and shift down the following elements.
+    /// Insert all elements of `iter` at position `index`.
     ///
-    /// The `index` must be strictly less than the length of the vector.
+    /// Shift up all elements after `index`, once; the `index` must be less
+    /// than or equal to the length.
     ///
-    /// ***Panics*** if the `index` is out of bounds.
+    /// The number of elements is taken from the iterator's reported length.
+    /// Extra elements are not consumed; if the iterator runs out early, fewer
+    /// elements are inserted.
+    ///
+    /// Returns an error with the iterator if the elements do not fit in the
+    /// remaining capacity; in that case the vector is left unchanged.
+    ///
+    /// ***Panics*** if `index` is out of bounds.
     ///
     /// ```
     /// use arrayvec::ArrayVec;
     ///
-    /// let mut array = ArrayVec::from([1, 2, 3]);
+    /// let mut array = ArrayVec::<_, 4>::new();
+    /// array.extend([0, 4].iter().copied());
     ///
-    /// let removed_elt = array.remove(0);
-    /// assert_eq!(removed_elt, 1);
-    /// assert_eq!(&array[..], &[2, 3]);
+    /// let err = array.try_insert_iter(1, 1..4).unwrap_err();
+    /// assert_eq!(err.element().collect::<Vec<_>>(), [1, 2, 3]);
+    ///
+    /// array.try_insert_iter(1, 2..4).unwrap();
+    /// assert_eq!(&array[..], &[0, 2, 3, 4]);
     /// ```
-    pub fn remove(&mut self, index: usize) -> T {
-        self.pop_at(index)
-            .unwrap_or_else(|| {
-                panic_oob!("remove", index, self.len())
-            })
+    #[track_caller]
+    pub fn try_insert_iter<I>(&mut self, index: usize, iter: I)
+        -> Result<(), CapacityError<I::IntoIter>>
+        where I: IntoIterator<Item = T>,
+              I::IntoIter: ExactSizeIterator,
+    {
+        let len = self.len();
+        if index > len {
+            panic_oob!("try_insert_iter", index, len)
+        }
+        let iter = iter.into_iter();
+        let n = iter.len();
+        if self.remaining_capacity() < n {
+            return Err(CapacityError::new(iter));
+        }
+        unsafe {
+            self.insert_from_iter(index, n, iter);
+        }
+        Ok(())
     }
 
-    /// Remove the element at `index` and shift down the following elements.
+    /// Shift the tail after `index` up by `n`, and fill the gap with at most
+    /// `n` elements from `iter`.
     ///
-    /// This is a checked version of `.remove(index)`. Returns `None` if there
-    /// is no element at `index`. Otherwise, return the element inside `Some`.
+    /// If `iter` panics or yields fewer than `n` elements, the tail is moved
+    /// back behind the elements that were written.
+    ///
+    /// ## Safety
+    ///
+    /// `index` must be less than or equal to the length, and `n` must not be
+    /// greater than the remaining capacity.
+    unsafe fn insert_from_iter<I>(&mut self, index: usize, n: usize, iter: I)
+        where I: Iterator<Item = T>
+    {
+        struct FillGapOnDrop<'a, T, const CAP: usize, L: LenType> {
+            v: &'a mut ArrayVec<T, CAP, L>,
+            index: usize,
+            written: usize,
+            gap: usize,
+            tail_len: usize,
+        }
+
+        impl<T, const CAP: usize, L: LenType> Drop for FillGapOnDrop<'_, T, CAP, L> {
+            fn drop(&mut self) {
+                unsafe {
+                    if self.written != self.gap {
+                        let p = self.v.as_mut_ptr().add(self.index);
+                        ptr::copy(p.add(self.gap), p.add(self.written), self.tail_len);
+                    }
+                    self.v.set_len(self.index + self.written + self.tail_len);
+                }
+            }
+        }
+
+        let len = self.len();
+        debug_assert!(index <= len && n <= self.remaining_capacity());
+        // Hide the tail while it is moved and the gap is being filled.
+        self.set_len(index);
+
+        let mut g = FillGapOnDrop { v: self, index, written: 0, gap: n, tail_len: len - index };
+        let p = g.v.as_mut_ptr().add(index);
+        ptr::copy(p, p.add(n), g.tail_len);
+        for elt in iter.take(n) {
+            p.add(g.written).write(elt);
+            g.written += 1;
+        }
+    }
+
+    /// Remove the last element in the vector and return it.
+    ///
+    /// Return `Some(` *element* `)` if the vector is non-empty, else `None`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 2>::new();
+    ///
+    /// array.push(1);
+    ///
+    /// assert_eq!(array.pop(), Some(1));
+    /// assert_eq!(array.pop(), None);
+    /// ```
+    pub const fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe {
+            let new_len = self.len() - 1;
+            self.set_len(new_len);
+            Some(ptr::read(self.as_ptr().add(new_len)))
+        }
+    }
+
+    /// Remove the element at `index` and swap the last element into its place.
+    ///
+    /// This operation is O(1).
+    ///
+    /// Return the *element* if the index is in bounds, else panic.
+    ///
+    /// ***Panics*** if the `index` is out of bounds.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::from([1, 2, 3]);
+    ///
+    /// assert_eq!(array.swap_remove(0), 1);
+    /// assert_eq!(&array[..], &[3, 2]);
+    ///
+    /// assert_eq!(array.swap_remove(1), 2);
+    /// assert_eq!(&array[..], &[3]);
+    /// ```
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        self.swap_pop(index)
+            .unwrap_or_else(|| {
+                panic_oob!("swap_remove", index, self.len())
+            })
+    }
+
+    /// Remove the element at `index` and swap the last element into its place.
+    ///
+    /// This is a checked version of `.swap_remove`.  
+    /// This operation is O(1).
+    ///
+    /// Return `Some(` *element* `)` if the index is in bounds, else `None`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::from([1, 2, 3]);
+    ///
+    /// assert_eq!(array.swap_pop(0), Some(1));
+    /// assert_eq!(&array[..], &[3, 2]);
+    ///
+    /// assert_eq!(array.swap_pop(10), None);
+    /// ```
+    pub fn swap_pop(&mut self, index: usize) -> Option<T> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        self.swap(index, len - 1);
+        self.pop()
+    }
+
+    /// Remove the element at `index` and shift down the following elements.
+    ///
+    /// The `index` must be strictly less than the length of the vector.
+    ///
+    /// ***Panics*** if the `index` is out of bounds.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::from([1, 2, 3]);
+    ///
+    /// let removed_elt = array.remove(0);
+    /// assert_eq!(removed_elt, 1);
+    /// assert_eq!(&array[..], &[2, 3]);
+    /// ```
+    pub fn remove(&mut self, index: usize) -> T {
+        self.pop_at(index)
+            .unwrap_or_else(|| {
+                panic_oob!("remove", index, self.len())
+            })
+    }
+
+    /// Remove the element at `index` and shift down the following elements.
+    ///
+    /// This is a checked version of `.remove(index)`. Returns `None` if there
+    /// is no element at `index`. Otherwise, return the element inside `Some`.
     ///
     /// ```
     /// use arrayvec::ArrayVec;
@@ -472,14 +1029,14 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
         let original_len = self.len();
         unsafe { self.set_len(0) };
 
-        struct BackshiftOnDrop<'a, T, const CAP: usize> {
-            v: &'a mut ArrayVec<T, CAP>,
+        struct BackshiftOnDrop<'a, T, const CAP: usize, L: LenType> {
+            v: &'a mut ArrayVec<T, CAP, L>,
             processed_len: usize,
             deleted_cnt: usize,
             original_len: usize,
         }
 
-        impl<T, const CAP: usize> Drop for BackshiftOnDrop<'_, T, CAP> {
+        impl<T, const CAP: usize, L: LenType> Drop for BackshiftOnDrop<'_, T, CAP, L> {
             fn drop(&mut self) {
                 if self.deleted_cnt > 0 {
                     unsafe {
@@ -499,9 +1056,9 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
         let mut g = BackshiftOnDrop { v: self, processed_len: 0, deleted_cnt: 0, original_len };
 
         #[inline(always)]
-        fn process_one<F: FnMut(&mut T) -> bool, T, const CAP: usize, const DELETED: bool>(
+        fn process_one<F: FnMut(&mut T) -> bool, T, const CAP: usize, L: LenType, const DELETED: bool>(
             f: &mut F,
-            g: &mut BackshiftOnDrop<'_, T, CAP>
+            g: &mut BackshiftOnDrop<'_, T, CAP, L>
         ) -> bool {
             let cur = unsafe { g.v.as_mut_ptr().add(g.processed_len) };
             if !f(unsafe { &mut *cur }) {
@@ -522,19 +1079,189 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
 
         // Stage 1: Nothing was deleted.
         while g.processed_len != original_len {
-            if !process_one::<F, T, CAP, false>(&mut f, &mut g) {
+            if !process_one::<F, T, CAP, L, false>(&mut f, &mut g) {
                 break;
             }
         }
 
         // Stage 2: Some elements were deleted.
         while g.processed_len != original_len {
-            process_one::<F, T, CAP, true>(&mut f, &mut g);
+            process_one::<F, T, CAP, L, true>(&mut f, &mut g);
+        }
+
+        drop(g);
+    }
+
+    /// Removes consecutive repeated elements in the vector according to the
+    /// `PartialEq` trait implementation.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::from([1, 2, 2, 3, 2, 2]);
+    /// array.dedup();
+    /// assert_eq!(&array[..], &[1, 2, 3, 2]);
+    /// ```
+    pub fn dedup(&mut self)
+        where T: PartialEq
+    {
+        self.dedup_by(|a, b| a == b)
+    }
+
+    /// Removes all but the first of consecutive elements in the vector that
+    /// resolve to the same key.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::from([10, 20, 21, 30, 20]);
+    /// array.dedup_by_key(|x| *x / 10);
+    /// assert_eq!(&array[..], &[10, 20, 30, 20]);
+    /// ```
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+        where F: FnMut(&mut T) -> K,
+              K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Removes all but the first of consecutive elements in the vector
+    /// satisfying a given equality relation.
+    ///
+    /// The `same_bucket` function is passed references to two elements from
+    /// the vector, the current one and the last one that was kept, and must
+    /// determine if the elements compare equal. If it returns true, the
+    /// current element is removed. The elements are passed in opposite order
+    /// from their order in the vector.
+    ///
+    /// This method operates in place, in a single pass.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::from(["foo", "bar", "Bar", "baz", "bar"]);
+    /// array.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    /// assert_eq!(&array[..], &["foo", "bar", "baz", "bar"]);
+    /// ```
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+        where F: FnMut(&mut T, &mut T) -> bool
+    {
+        // Compaction mirrors `retain`: elements before `write` are kept,
+        // elements from `read` on are not yet visited, and the gap between
+        // them holds removed (dropped) elements.
+        let original_len = self.len();
+        if original_len <= 1 {
+            return;
+        }
+        unsafe { self.set_len(0) };
+
+        struct FillGapOnDrop<'a, T, const CAP: usize, L: LenType> {
+            v: &'a mut ArrayVec<T, CAP, L>,
+            read: usize,
+            write: usize,
+            original_len: usize,
+        }
+
+        impl<T, const CAP: usize, L: LenType> Drop for FillGapOnDrop<'_, T, CAP, L> {
+            fn drop(&mut self) {
+                let removed = self.read - self.write;
+                if removed > 0 {
+                    unsafe {
+                        ptr::copy(
+                            self.v.as_ptr().add(self.read),
+                            self.v.as_mut_ptr().add(self.write),
+                            self.original_len - self.read
+                        );
+                    }
+                }
+                unsafe {
+                    self.v.set_len(self.original_len - removed);
+                }
+            }
+        }
+
+        // The first element is always kept.
+        let mut g = FillGapOnDrop { v: self, read: 1, write: 1, original_len };
+
+        while g.read != original_len {
+            let ptr = g.v.as_mut_ptr();
+            let cur = unsafe { ptr.add(g.read) };
+            let prev = unsafe { ptr.add(g.write - 1) };
+            if same_bucket(unsafe { &mut *cur }, unsafe { &mut *prev }) {
+                // Advance before dropping, so that the element is not
+                // dropped again if its destructor panics.
+                g.read += 1;
+                unsafe { ptr::drop_in_place(cur) };
+            } else {
+                if g.read != g.write {
+                    unsafe {
+                        ptr::copy_nonoverlapping(cur, ptr.add(g.write), 1);
+                    }
+                }
+                g.read += 1;
+                g.write += 1;
+            }
         }
 
         drop(g);
     }
 
+    /// Create an iterator that removes the elements in `range` for which the
+    /// predicate returns true, and yields the removed items by value.
+    ///
+    /// Elements for which the predicate returns false are kept and compacted
+    /// in place, preserving their order. The predicate may mutate the
+    /// elements it is passed.
+    ///
+    /// If the `ExtractIf` is dropped before it is exhausted, the remaining
+    /// elements are kept. If the predicate panics, the elements that were not
+    /// yet visited are kept as well.
+    ///
+    /// Note: It is unspecified how many elements are removed from the vector,
+    /// if the `ExtractIf` value is leaked.
+    ///
+    /// **Panics** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut v = ArrayVec::from([1, 2, 3, 4, 5, 6]);
+    /// let evens: ArrayVec<_, 6> = v.extract_if(.., |x| *x % 2 == 0).collect();
+    /// assert_eq!(&v[..], &[1, 3, 5]);
+    /// assert_eq!(&evens[..], &[2, 4, 6]);
+    ///
+    /// let big: ArrayVec<_, 6> = v.extract_if(1.., |x| *x > 2).collect();
+    /// assert_eq!(&v[..], &[1]);
+    /// assert_eq!(&big[..], &[3, 5]);
+    /// ```
+    pub fn extract_if<R, F>(&mut self, range: R, pred: F) -> ExtractIf<'_, T, F, CAP, L>
+        where R: RangeBounds<usize>,
+              F: FnMut(&mut T) -> bool,
+    {
+        let old_len = self.len();
+        let (start, end) = range_to_indices(&range, old_len);
+        // bounds check happens here (before length is changed!)
+        let _ = &self[start..end];
+
+        // Like `retain`, guard against the predicate panicking or the
+        // `ExtractIf` being leaked by hiding all elements until it is dropped.
+        unsafe { self.set_len(0) };
+
+        ExtractIf {
+            vec: self,
+            idx: start,
+            end,
+            del: 0,
+            old_len,
+            pred,
+        }
+    }
+
     /// Returns the remaining spare capacity of the vector as a slice of
     /// `MaybeUninit<T>`.
     ///
@@ -570,142 +1297,851 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
         &mut self.xs[len..]
     }
 
-    /// Set the vector’s length without dropping or moving out elements
+    /// Set the vector’s length without dropping or moving out elements
+    ///
+    /// This method is `unsafe` because it changes the notion of the
+    /// number of “valid” elements in the vector. Use with care.
+    ///
+    /// This method uses *debug assertions* to check that `length` is
+    /// not greater than the capacity.
+    pub const unsafe fn set_len(&mut self, length: usize) {
+        // type invariant that capacity always fits in L
+        debug_assert!(length <= self.capacity());
+        self.len = len_from_usize(length);
+    }
+
+    /// Copy all elements from the slice and append to the `ArrayVec`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut vec: ArrayVec<usize, 10> = ArrayVec::new();
+    /// vec.push(1);
+    /// vec.try_extend_from_slice(&[2, 3]).unwrap();
+    /// assert_eq!(&vec[..], &[1, 2, 3]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the capacity left (see
+    /// [`remaining_capacity`]) is smaller then the length of the provided
+    /// slice.
+    ///
+    /// [`remaining_capacity`]: #method.remaining_capacity
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError>
+        where T: Copy,
+    {
+        if self.remaining_capacity() < other.len() {
+            return Err(CapacityError::new(()));
+        }
+
+        let self_len = self.len();
+        let other_len = other.len();
+
+        unsafe {
+            let dst = self.get_unchecked_ptr(self_len);
+            ptr::copy_nonoverlapping(other.as_ptr(), dst, other_len);
+            self.set_len(self_len + other_len);
+        }
+        Ok(())
+    }
+
+    /// Clone all elements from the slice and append to the `ArrayVec`.
+    ///
+    /// This is the `Clone` counterpart of `try_extend_from_slice`. If cloning
+    /// an element panics, the elements cloned so far are kept.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut vec: ArrayVec<String, 3> = ArrayVec::new();
+    /// vec.push("a".to_string());
+    /// vec.try_extend_from_slice_cloned(&["b".to_string(), "c".to_string()]).unwrap();
+    /// assert_eq!(&vec[..], &["a", "b", "c"]);
+    /// assert!(vec.try_extend_from_slice_cloned(&["d".to_string()]).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the capacity left (see
+    /// [`remaining_capacity`]) is smaller then the length of the provided
+    /// slice; in that case nothing is appended.
+    ///
+    /// [`remaining_capacity`]: #method.remaining_capacity
+    pub fn try_extend_from_slice_cloned(&mut self, other: &[T]) -> Result<(), CapacityError>
+        where T: Clone,
+    {
+        if self.remaining_capacity() < other.len() {
+            return Err(CapacityError::new(()));
+        }
+        self.extend_from_slice(other);
+        Ok(())
+    }
+
+    /// Clone as many elements from the start of the slice as fit in the
+    /// remaining capacity and append them to the `ArrayVec`, and return the
+    /// rest of the slice.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut vec: ArrayVec<i32, 3> = ArrayVec::new();
+    /// assert_eq!(vec.extend_from_slice_truncating(&[1, 2]), &[]);
+    /// assert_eq!(vec.extend_from_slice_truncating(&[3, 4, 5]), &[4, 5]);
+    /// assert_eq!(&vec[..], &[1, 2, 3]);
+    /// ```
+    pub fn extend_from_slice_truncating<'a>(&mut self, other: &'a [T]) -> &'a [T]
+        where T: Clone,
+    {
+        let take = cmp::min(self.remaining_capacity(), other.len());
+        let (head, rest) = other.split_at(take);
+        self.extend_from_slice(head);
+        rest
+    }
+
+    /// Extend the vector with all elements of `iter`, or with none of them.
+    ///
+    /// If the elements do not fit, the vector is rolled back to its original
+    /// length (dropping the elements that were added) and an error is
+    /// returned, holding the first element that did not fit and the rest of
+    /// the iterator.
+    ///
+    /// If the lower bound of the iterator's `size_hint` shows that the
+    /// elements can not fit, the error is returned up front, without taking
+    /// any element from the iterator; the error's element is `None` then.
+    ///
+    /// If the iterator panics, the vector is rolled back as well.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut vec: ArrayVec<i32, 4> = ArrayVec::new();
+    /// vec.try_extend(0..2).unwrap();
+    ///
+    /// // rejected up front
+    /// let (overflow, rest) = vec.try_extend(2..5).unwrap_err().element();
+    /// assert_eq!(overflow, None);
+    /// assert_eq!(rest, 2..5);
+    ///
+    /// // rolled back
+    /// let (overflow, rest) = vec.try_extend((2..5).filter(|_| true)).unwrap_err().element();
+    /// assert_eq!(overflow, Some(4));
+    /// assert_eq!(rest.count(), 0);
+    /// assert_eq!(&vec[..], &[0, 1]);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), CapacityError<(Option<T>, I::IntoIter)>>
+        where I: IntoIterator<Item = T>
+    {
+        let mut iter = iter.into_iter();
+        let take = self.remaining_capacity();
+        if iter.size_hint().0 > take {
+            return Err(CapacityError::new((None, iter)));
+        }
+
+        // The checkpoint rolls back on drop, also if the iterator panics.
+        let mut checkpoint = self.checkpoint();
+        unsafe {
+            checkpoint.extend_from_iter::<_, false>(iter.by_ref().take(take));
+        }
+        match iter.next() {
+            Some(elt) => {
+                checkpoint.rollback();
+                Err(CapacityError::new((Some(elt), iter)))
+            }
+            None => {
+                checkpoint.commit();
+                Ok(())
+            }
+        }
+    }
+
+    /// Extend the vector with the elements of `iter` until it is full, and
+    /// return the rest of the iterator.
+    ///
+    /// No more elements are taken from the iterator than fit in the vector.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut vec: ArrayVec<i32, 3> = ArrayVec::new();
+    /// vec.push(0);
+    /// let mut rest = vec.extend_until_full(1..6);
+    /// assert_eq!(&vec[..], &[0, 1, 2]);
+    /// assert_eq!(rest.next(), Some(3));
+    /// ```
+    pub fn extend_until_full<I>(&mut self, iter: I) -> I::IntoIter
+        where I: IntoIterator<Item = T>
+    {
+        let mut iter = iter.into_iter();
+        let take = self.remaining_capacity();
+        unsafe {
+            self.extend_from_iter::<_, false>(iter.by_ref().take(take));
+        }
+        iter
+    }
+
+    /// Clone the elements in `range` and append them to the end of the vector.
+    ///
+    /// ***Panics*** if the starting point is greater than the end point, if
+    /// the end point is greater than the length of the vector, or if the
+    /// elements do not fit in the remaining capacity. See
+    /// `try_extend_from_within` for fallible version.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut vec = ArrayVec::<_, 8>::new();
+    /// vec.extend([0, 1, 2].iter().copied());
+    /// vec.extend_from_within(1..);
+    /// assert_eq!(&vec[..], &[0, 1, 2, 1, 2]);
+    /// ```
+    #[track_caller]
+    pub fn extend_from_within<R>(&mut self, range: R)
+        where T: Clone,
+              R: RangeBounds<usize>,
+    {
+        self.try_extend_from_within(range).unwrap()
+    }
+
+    /// Clone the elements in `range` and append them to the end of the vector.
+    ///
+    /// If cloning an element panics, the elements cloned so far are kept.
+    ///
+    /// Returns an error if the elements do not fit in the remaining
+    /// capacity; in that case nothing is appended.
+    ///
+    /// ***Panics*** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut vec = ArrayVec::<_, 4>::new();
+    /// vec.extend(["a", "b", "c"].iter().map(|s| s.to_string()));
+    /// assert!(vec.try_extend_from_within(..2).is_err());
+    /// vec.try_extend_from_within(..1).unwrap();
+    /// assert_eq!(&vec[..], &["a", "b", "c", "a"]);
+    /// ```
+    #[track_caller]
+    pub fn try_extend_from_within<R>(&mut self, range: R) -> Result<(), CapacityError>
+        where T: Clone,
+              R: RangeBounds<usize>,
+    {
+        let len = self.len();
+        let (start, end) = range_to_indices(&range, len);
+        // bounds check
+        let _ = &self[start..end];
+        if self.remaining_capacity() < end - start {
+            return Err(CapacityError::new(()));
+        }
+
+        let ptr = self.as_mut_ptr();
+        // WritebackGuard updates self.len on drop (both success and panic).
+        let mut guard = WritebackGuard { target: &mut self.len, len };
+        for i in start..end {
+            unsafe {
+                let elt = (*ptr.add(i)).clone();
+                ptr.add(guard.len).write(elt);
+            }
+            guard.len += 1;
+        }
+        Ok(())
+    }
+
+    /// Split the vector in two at `at`, moving the elements `[at, len)` into a
+    /// new vector of capacity `N`.
+    ///
+    /// Returns an error if the split off elements do not fit in the new
+    /// vector; in that case `self` is left unchanged.
+    ///
+    /// ***Panics*** if `at` is greater than the length.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::from([1, 2, 3, 4, 5]);
+    ///
+    /// let tail = array.split_off::<3>(2).unwrap();
+    /// assert_eq!(&array[..], &[1, 2]);
+    /// assert_eq!(&tail[..], &[3, 4, 5]);
+    ///
+    /// assert!(array.split_off::<1>(0).is_err());
+    /// assert_eq!(&array[..], &[1, 2]);
+    /// ```
+    #[track_caller]
+    pub fn split_off<const N: usize>(&mut self, at: usize) -> Result<ArrayVec<T, N, L>, CapacityError> {
+        let len = self.len();
+        if at > len {
+            panic_oob!("split_off", at, len)
+        }
+        let other_len = len - at;
+        if other_len > N {
+            return Err(CapacityError::new(()));
+        }
+
+        let mut other = ArrayVec::new();
+        unsafe {
+            ptr::copy_nonoverlapping(self.as_ptr().add(at), other.as_mut_ptr(), other_len);
+            self.set_len(at);
+            other.set_len(other_len);
+        }
+        Ok(other)
+    }
+
+    /// Move all elements of `other` to the end of the vector, leaving `other`
+    /// empty.
+    ///
+    /// ***Panics*** if the elements do not fit in the remaining capacity. See
+    /// `try_append` for fallible version.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 8>::new();
+    /// array.push(1);
+    /// let mut other = ArrayVec::from([2, 3]);
+    ///
+    /// array.append(&mut other);
+    /// assert_eq!(&array[..], &[1, 2, 3]);
+    /// assert!(other.is_empty());
+    /// ```
+    #[track_caller]
+    pub fn append<const M: usize>(&mut self, other: &mut ArrayVec<T, M, L>) {
+        self.try_append(other).unwrap()
+    }
+
+    /// Move all elements of `other` to the end of the vector, leaving `other`
+    /// empty.
+    ///
+    /// Returns an error if the elements do not fit in the remaining capacity;
+    /// in that case both vectors are left unchanged.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 3>::new();
+    /// array.push(1);
+    ///
+    /// let mut other = ArrayVec::from([2, 3, 4]);
+    /// assert!(array.try_append(&mut other).is_err());
+    /// assert_eq!(&other[..], &[2, 3, 4]);
+    ///
+    /// other.pop();
+    /// array.try_append(&mut other).unwrap();
+    /// assert_eq!(&array[..], &[1, 2, 3]);
+    /// assert!(other.is_empty());
+    /// ```
+    pub fn try_append<const M: usize>(&mut self, other: &mut ArrayVec<T, M, L>)
+        -> Result<(), CapacityError>
+    {
+        let other_len = other.len();
+        if self.remaining_capacity() < other_len {
+            return Err(CapacityError::new(()));
+        }
+
+        let self_len = self.len();
+        unsafe {
+            let dst = self.get_unchecked_ptr(self_len);
+            ptr::copy_nonoverlapping(other.as_ptr(), dst, other_len);
+            other.set_len(0);
+            self.set_len(self_len + other_len);
+        }
+        Ok(())
+    }
+
+    /// Move as many elements from the front of `other` to the end of the
+    /// vector as fit in the remaining capacity, and return how many were
+    /// moved.
+    ///
+    /// The elements left in `other` are shifted down to its start.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 3>::new();
+    /// array.push(1);
+    ///
+    /// let mut other = ArrayVec::from([2, 3, 4, 5]);
+    /// assert_eq!(array.move_from(&mut other), 2);
+    /// assert_eq!(&array[..], &[1, 2, 3]);
+    /// assert_eq!(&other[..], &[4, 5]);
+    /// ```
+    pub fn move_from<const M: usize>(&mut self, other: &mut ArrayVec<T, M, L>) -> usize {
+        let other_len = other.len();
+        let count = cmp::min(self.remaining_capacity(), other_len);
+        if count == 0 {
+            return 0;
+        }
+
+        let self_len = self.len();
+        unsafe {
+            let dst = self.get_unchecked_ptr(self_len);
+            let src = other.as_mut_ptr();
+            ptr::copy_nonoverlapping(src, dst, count);
+            ptr::copy(src.add(count), src, other_len - count);
+            other.set_len(other_len - count);
+            self.set_len(self_len + count);
+        }
+        count
+    }
+
+    /// Create a draining iterator that removes the specified range in the vector
+    /// and yields the removed items from start to end. The element range is
+    /// removed even if the iterator is not consumed until the end.
+    ///
+    /// Note: It is unspecified how many elements are removed from the vector,
+    /// if the `Drain` value is leaked.
+    ///
+    /// **Panics** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut v1 = ArrayVec::from([1, 2, 3]);
+    /// let v2: ArrayVec<_, 3> = v1.drain(0..2).collect();
+    /// assert_eq!(&v1[..], &[3]);
+    /// assert_eq!(&v2[..], &[1, 2]);
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, CAP, L>
+        where R: RangeBounds<usize>
+    {
+        // Memory safety
+        //
+        // When the Drain is first created, it shortens the length of
+        // the source vector to make sure no uninitialized or moved-from elements
+        // are accessible at all if the Drain's destructor never gets to run.
+        //
+        // Drain will ptr::read out the values to remove.
+        // When finished, remaining tail of the vec is copied back to cover
+        // the hole, and the vector length is restored to the new length.
+        //
+        let (start, end) = range_to_indices(&range, self.len());
+        self.drain_range(start, end)
+    }
+
+    fn drain_range(&mut self, start: usize, end: usize) -> Drain<'_, T, CAP, L>
+    {
+        let len = self.len();
+
+        // bounds check happens here (before length is changed!)
+        let range_slice: *const _ = &self[start..end];
+
+        // Calling `set_len` creates a fresh and thus unique mutable references, making all
+        // older aliases we created invalid. So we cannot call that function.
+        self.len = len_from_usize(start);
+
+        unsafe {
+            Drain {
+                tail_start: end,
+                tail_len: len - end,
+                iter: (*range_slice).iter(),
+                vec: self as *mut _,
+            }
+        }
+    }
+
+    /// Create a splicing iterator that replaces the specified range in the vector
+    /// with the elements of `replace_with`, and yields the removed items.
+    /// `replace_with` does not need to have the same length as `range`.
+    ///
+    /// The element range is removed even if the iterator is not consumed until
+    /// the end. The replacement elements are inserted when the `Splice` value
+    /// is dropped, and `replace_with` is only consumed then.
+    ///
+    /// Note: It is unspecified how many elements are removed from the vector,
+    /// if the `Splice` value is leaked.
+    ///
+    /// **Panics** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// ***Panics*** when the `Splice` is dropped, if the replacement elements
+    /// do not fit in the vector's capacity. Overflow is only detected then, and
+    /// the panic is raised from `Drop`: if the `Splice` is dropped while the
+    /// thread is already panicking, this is a double panic, which aborts the
+    /// process. Use `try_splice` with an `ExactSizeIterator` to check the
+    /// capacity up front instead.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut v = ArrayVec::<_, 8>::new();
+    /// v.extend([1, 2, 3, 4].iter().copied());
+    /// let removed: Vec<_> = v.splice(1..3, [7, 8, 9].iter().copied()).collect();
+    /// assert_eq!(&v[..], &[1, 7, 8, 9, 4]);
+    /// assert_eq!(&removed[..], &[2, 3]);
+    /// ```
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter, CAP, L>
+        where R: RangeBounds<usize>,
+              I: IntoIterator<Item = T>,
+    {
+        Splice {
+            drain: self.drain(range),
+            replace_with: replace_with.into_iter(),
+        }
+    }
+
+    /// Create a splicing iterator that replaces the specified range in the vector
+    /// with the elements of `replace_with`, and yields the removed items.
+    ///
+    /// This is a checked version of `.splice()`: the length of `replace_with`
+    /// is checked against the capacity up front. If the replacement elements
+    /// do not fit, the vector is left untouched and the iterator is returned
+    /// in the error.
+    ///
+    /// **Panics** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector; or, when the
+    /// `Splice` is dropped, if `replace_with` yields more elements than its
+    /// reported length and they do not fit.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut v = ArrayVec::from([1, 2, 3, 4]);
+    ///
+    /// assert!(v.try_splice(1..2, [7, 8].iter().copied()).is_err());
+    /// assert_eq!(&v[..], &[1, 2, 3, 4]);
+    ///
+    /// let removed: Vec<_> = v.try_splice(1..3, [7, 8].iter().copied()).unwrap().collect();
+    /// assert_eq!(&v[..], &[1, 7, 8, 4]);
+    /// assert_eq!(&removed[..], &[2, 3]);
+    /// ```
+    pub fn try_splice<R, I>(&mut self, range: R, replace_with: I)
+        -> Result<Splice<'_, I::IntoIter, CAP, L>, CapacityError<I::IntoIter>>
+        where R: RangeBounds<usize>,
+              I: IntoIterator<Item = T>,
+              I::IntoIter: ExactSizeIterator,
+    {
+        let replace_with = replace_with.into_iter();
+        let len = self.len();
+        let (start, end) = range_to_indices(&range, len);
+        // an invalid range is caught by the bounds check in drain_range
+        if start <= end && end <= len && len - (end - start) + replace_with.len() > CAP {
+            return Err(CapacityError::new(replace_with));
+        }
+        Ok(Splice {
+            drain: self.drain_range(start, end),
+            replace_with,
+        })
+    }
+
+    /// Return the inner fixed size array, if it is full to its capacity.
+    ///
+    /// Return an `Ok` value with the array if length equals capacity,
+    /// return an `Err` with self otherwise.
+    pub fn into_inner(self) -> Result<[T; CAP], Self> {
+        if self.len() < self.capacity() {
+            Err(self)
+        } else {
+            unsafe { Ok(self.into_inner_unchecked()) }
+        }
+    }
+
+    /// Return the inner fixed size array.
+    ///
+    /// Safety:
+    /// This operation is safe if and only if length equals capacity.
+    pub unsafe fn into_inner_unchecked(self) -> [T; CAP] {
+        debug_assert_eq!(self.len(), self.capacity());
+        let self_ = ManuallyDrop::new(self);
+        let array = ptr::read(self_.as_ptr() as *const [T; CAP]);
+        array
+    }
+
+    /// Move the elements into a new `ArrayVec` with capacity `N`.
+    ///
+    /// Return an `Ok` value with the new vector if the elements fit in it,
+    /// return an `Err` with self otherwise.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 8>::new();
+    /// array.extend(0..3);
+    ///
+    /// let small = array.into_capacity::<4>().unwrap();
+    /// assert_eq!(&small[..], &[0, 1, 2]);
+    /// assert!(small.into_capacity::<2>().is_err());
+    /// ```
+    pub fn into_capacity<const N: usize>(self) -> Result<ArrayVec<T, N, L>, Self> {
+        if self.len() > N {
+            Err(self)
+        } else {
+            unsafe { Ok(self.into_capacity_unchecked()) }
+        }
+    }
+
+    /// Move the elements into a new `ArrayVec` with capacity `N`, which must
+    /// not be smaller than `CAP`.
+    ///
+    /// Using a capacity `N` that is smaller than `CAP` is a compile time error.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let array = ArrayVec::from([1, 2, 3]);
+    /// let wide = array.widen::<8>();
+    /// assert_eq!(&wide[..], &[1, 2, 3]);
+    /// assert_eq!(wide.capacity(), 8);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let array = ArrayVec::from([1, 2, 3]);
+    /// let narrow = array.widen::<2>();
+    /// ```
+    pub fn widen<const N: usize>(self) -> ArrayVec<T, N, L> {
+        let () = CapacityFits::<CAP, N>::FITS;
+        unsafe { self.into_capacity_unchecked() }
+    }
+
+    /// Safety: the length must not be greater than `N`.
+    unsafe fn into_capacity_unchecked<const N: usize>(self) -> ArrayVec<T, N, L> {
+        debug_assert!(self.len() <= N);
+        let len = self.len();
+        let self_ = ManuallyDrop::new(self);
+        let mut array = ArrayVec::new();
+        ptr::copy_nonoverlapping(self_.as_ptr(), array.as_mut_ptr(), len);
+        array.set_len(len);
+        array
+    }
+
+    /// Return a new `ArrayVec` with the same capacity, with `f` applied to
+    /// each element in order.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let array = ArrayVec::from([1, 2, 3]);
+    /// let strings = array.map(|x| x.to_string());
+    /// assert_eq!(&strings[..], &["1", "2", "3"]);
+    /// ```
+    pub fn map<U, F>(self, f: F) -> ArrayVec<U, CAP, L>
+        where F: FnMut(T) -> U
+    {
+        let mut array = ArrayVec::new();
+        unsafe {
+            array.extend_from_iter::<_, false>(self.into_iter().map(f));
+        }
+        array
+    }
+
+    /// Return a new `ArrayVec` with the same capacity, with the fallible
+    /// function `f` applied to each element in order.
+    ///
+    /// Stops at the first error and returns it; the elements that were
+    /// already mapped and the ones not yet mapped are dropped.
+    ///
+    /// See [`try_map_opt`](ArrayVec::try_map_opt) for a function returning
+    /// `Option`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let array = ArrayVec::from(["1", "2", "3"]);
+    /// let numbers = array.try_map(|s| s.parse::<u8>()).unwrap();
+    /// assert_eq!(&numbers[..], &[1, 2, 3]);
+    ///
+    /// let array = ArrayVec::from(["1", "x", "3"]);
+    /// assert!(array.try_map(|s| s.parse::<u8>()).is_err());
+    /// ```
+    pub fn try_map<U, E, F>(self, mut f: F) -> Result<ArrayVec<U, CAP, L>, E>
+        where F: FnMut(T) -> Result<U, E>
+    {
+        let mut array = ArrayVec::new();
+        for elt in self {
+            let elt = f(elt)?;
+            unsafe {
+                array.push_unchecked(elt);
+            }
+        }
+        Ok(array)
+    }
+
+    /// Return a new `ArrayVec` with the same capacity, with the function `f`
+    /// applied to each element in order, or `None` if `f` returns `None`.
+    ///
+    /// Stops at the first `None`, like [`try_map`](ArrayVec::try_map).
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let array = ArrayVec::from([1, 3, 2]);
+    /// let inverse = array.try_map_opt(|x| 12u8.checked_div(x));
+    /// assert_eq!(inverse, Some(ArrayVec::from([12, 4, 6])));
+    ///
+    /// let array = ArrayVec::from([1, 0, 2]);
+    /// assert_eq!(array.try_map_opt(|x| 12u8.checked_div(x)), None);
+    /// ```
+    pub fn try_map_opt<U, F>(self, mut f: F) -> Option<ArrayVec<U, CAP, L>>
+        where F: FnMut(T) -> Option<U>
+    {
+        self.try_map(|elt| f(elt).ok_or(())).ok()
+    }
+
+    /// Return a new `ArrayVec` with the same capacity, with pairs of elements
+    /// from `self` and `other`.
+    ///
+    /// Stops when either `self` or `other` runs out; the remaining elements
+    /// of `self` are dropped.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let array = ArrayVec::from([1, 2, 3]);
+    /// let pairs = array.zip(ArrayVec::from(["a", "b"]));
+    /// assert_eq!(&pairs[..], &[(1, "a"), (2, "b")]);
+    /// assert_eq!(pairs.capacity(), 3);
+    /// ```
+    pub fn zip<I>(self, other: I) -> ArrayVec<(T, I::Item), CAP, L>
+        where I: IntoIterator
+    {
+        let mut array = ArrayVec::new();
+        unsafe {
+            array.extend_from_iter::<_, false>(self.into_iter().zip(other));
+        }
+        array
+    }
+
+    /// Split the vector into two vectors with the same capacity: the
+    /// elements for which `pred` returns true, and the ones for which it
+    /// returns false. The order of the elements is preserved.
     ///
-    /// This method is `unsafe` because it changes the notion of the
-    /// number of “valid” elements in the vector. Use with care.
+    /// ```
+    /// use arrayvec::ArrayVec;
     ///
-    /// This method uses *debug assertions* to check that `length` is
-    /// not greater than the capacity.
-    pub unsafe fn set_len(&mut self, length: usize) {
-        // type invariant that capacity always fits in LenUint
-        debug_assert!(length <= self.capacity());
-        self.len = length as LenUint;
+    /// let array = ArrayVec::from([1, 2, 3, 4, 5]);
+    /// let (even, odd) = array.partition(|x| *x % 2 == 0);
+    /// assert_eq!(&even[..], &[2, 4]);
+    /// assert_eq!(&odd[..], &[1, 3, 5]);
+    /// ```
+    pub fn partition<F>(self, mut pred: F) -> (Self, Self)
+        where F: FnMut(&T) -> bool
+    {
+        let mut left = Self::new();
+        let mut right = Self::new();
+        for elt in self {
+            let target = if pred(&elt) { &mut left } else { &mut right };
+            unsafe {
+                target.push_unchecked(elt);
+            }
+        }
+        (left, right)
     }
 
-    /// Copy all elements from the slice and append to the `ArrayVec`.
+    /// Move the elements for which `pred` returns true to the end of `left`,
+    /// and the ones for which it returns false to the end of `right`. The
+    /// order of the elements is preserved.
     ///
-    /// ```
-    /// use arrayvec::ArrayVec;
+    /// Returns an error if an element does not fit in its target. The error
+    /// holds that element, followed by the elements not yet visited; the
+    /// elements that were already moved stay in `left` and `right`.
     ///
-    /// let mut vec: ArrayVec<usize, 10> = ArrayVec::new();
-    /// vec.push(1);
-    /// vec.try_extend_from_slice(&[2, 3]).unwrap();
-    /// assert_eq!(&vec[..], &[1, 2, 3]);
     /// ```
+    /// use arrayvec::ArrayVec;
     ///
-    /// # Errors
+    /// let mut small = ArrayVec::<_, 2>::new();
+    /// let mut large = ArrayVec::<_, 4>::new();
     ///
-    /// This method will return an error if the capacity left (see
-    /// [`remaining_capacity`]) is smaller then the length of the provided
-    /// slice.
+    /// let array = ArrayVec::from([1, 20, 30, 4, 50]);
+    /// array.try_partition_into(&mut small, &mut large, |x| *x < 10).unwrap();
+    /// assert_eq!(&small[..], &[1, 4]);
+    /// assert_eq!(&large[..], &[20, 30, 50]);
     ///
-    /// [`remaining_capacity`]: #method.remaining_capacity
-    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError>
-        where T: Copy,
+    /// let array = ArrayVec::from([5, 60, 7]);
+    /// let rest = array.try_partition_into(&mut small, &mut large, |x| *x < 10);
+    /// assert_eq!(&rest.unwrap_err().element()[..], &[5, 60, 7]);
+    /// ```
+    pub fn try_partition_into<F, const M: usize, const N: usize>(
+        self,
+        left: &mut ArrayVec<T, M, L>,
+        right: &mut ArrayVec<T, N, L>,
+        mut pred: F,
+    ) -> Result<(), CapacityError<Self>>
+        where F: FnMut(&T) -> bool
     {
-        if self.remaining_capacity() < other.len() {
-            return Err(CapacityError::new(()));
+        let mut iter = self.into_iter();
+        let mut overflow = None;
+        for elt in iter.by_ref() {
+            let res = if pred(&elt) { left.try_push(elt) } else { right.try_push(elt) };
+            if let Err(err) = res {
+                overflow = Some(err.element());
+                break;
+            }
         }
-
-        let self_len = self.len();
-        let other_len = other.len();
-
-        unsafe {
-            let dst = self.get_unchecked_ptr(self_len);
-            ptr::copy_nonoverlapping(other.as_ptr(), dst, other_len);
-            self.set_len(self_len + other_len);
+        match overflow {
+            Some(elt) => {
+                let mut rest = Self::new();
+                unsafe {
+                    rest.push_unchecked(elt);
+                    rest.extend_from_iter::<_, false>(iter);
+                }
+                Err(CapacityError::new(rest))
+            }
+            None => Ok(()),
         }
-        Ok(())
     }
 
-    /// Create a draining iterator that removes the specified range in the vector
-    /// and yields the removed items from start to end. The element range is
-    /// removed even if the iterator is not consumed until the end.
+    /// Save the current length of the vector, and return a guard that
+    /// truncates the vector back to it when dropped, unless it is committed.
     ///
-    /// Note: It is unspecified how many elements are removed from the vector,
-    /// if the `Drain` value is leaked.
-    ///
-    /// **Panics** if the starting point is greater than the end point or if
-    /// the end point is greater than the length of the vector.
+    /// The guard dereferences to the vector, so it can be used to add
+    /// elements. Rolling back only removes elements past the saved length;
+    /// changes to the elements before it are kept.
     ///
     /// ```
     /// use arrayvec::ArrayVec;
     ///
-    /// let mut v1 = ArrayVec::from([1, 2, 3]);
-    /// let v2: ArrayVec<_, 3> = v1.drain(0..2).collect();
-    /// assert_eq!(&v1[..], &[3]);
-    /// assert_eq!(&v2[..], &[1, 2]);
+    /// let mut array = ArrayVec::<u8, 8>::new();
+    /// array.push(1);
+    ///
+    /// let mut checkpoint = array.checkpoint();
+    /// checkpoint.push(2);
+    /// drop(checkpoint);
+    /// assert_eq!(&array[..], &[1]);
+    ///
+    /// let mut checkpoint = array.checkpoint();
+    /// checkpoint.push(2);
+    /// checkpoint.commit();
+    /// assert_eq!(&array[..], &[1, 2]);
     /// ```
-    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, CAP>
-        where R: RangeBounds<usize>
-    {
-        // Memory safety
-        //
-        // When the Drain is first created, it shortens the length of
-        // the source vector to make sure no uninitialized or moved-from elements
-        // are accessible at all if the Drain's destructor never gets to run.
-        //
-        // Drain will ptr::read out the values to remove.
-        // When finished, remaining tail of the vec is copied back to cover
-        // the hole, and the vector length is restored to the new length.
-        //
-        let len = self.len();
-        let start = match range.start_bound() {
-            Bound::Unbounded => 0,
-            Bound::Included(&i) => i,
-            Bound::Excluded(&i) => i.saturating_add(1),
-        };
-        let end = match range.end_bound() {
-            Bound::Excluded(&j) => j,
-            Bound::Included(&j) => j.saturating_add(1),
-            Bound::Unbounded => len,
-        };
-        self.drain_range(start, end)
-    }
-
-    fn drain_range(&mut self, start: usize, end: usize) -> Drain<'_, T, CAP>
-    {
+    pub fn checkpoint(&mut self) -> Checkpoint<'_, T, CAP, L> {
         let len = self.len();
-
-        // bounds check happens here (before length is changed!)
-        let range_slice: *const _ = &self[start..end];
-
-        // Calling `set_len` creates a fresh and thus unique mutable references, making all
-        // older aliases we created invalid. So we cannot call that function.
-        self.len = start as LenUint;
-
-        unsafe {
-            Drain {
-                tail_start: end,
-                tail_len: len - end,
-                iter: (*range_slice).iter(),
-                vec: self as *mut _,
-            }
-        }
+        Checkpoint { vec: self, len }
     }
 
-    /// Return the inner fixed size array, if it is full to its capacity.
+    /// Call `f` with the vector, and roll back the elements it added if it
+    /// returns an error or panics.
     ///
-    /// Return an `Ok` value with the array if length equals capacity,
-    /// return an `Err` with self otherwise.
-    pub fn into_inner(self) -> Result<[T; CAP], Self> {
-        if self.len() < self.capacity() {
-            Err(self)
-        } else {
-            unsafe { Ok(self.into_inner_unchecked()) }
-        }
-    }
-
-    /// Return the inner fixed size array.
+    /// See [`ArrayVec::checkpoint`] for details.
     ///
-    /// Safety:
-    /// This operation is safe if and only if length equals capacity.
-    pub unsafe fn into_inner_unchecked(self) -> [T; CAP] {
-        debug_assert_eq!(self.len(), self.capacity());
-        let self_ = ManuallyDrop::new(self);
-        let array = ptr::read(self_.as_ptr() as *const [T; CAP]);
-        array
+    /// ```
+    /// use arrayvec::{ArrayVec, CapacityError};
+    ///
+    /// fn encode(buf: &mut ArrayVec<u8, 4>, fields: &[&[u8]]) -> Result<(), CapacityError> {
+    ///     buf.transaction(|buf| {
+    ///         for field in fields {
+    ///             buf.try_push(field.len() as u8).map_err(|e| e.simplify())?;
+    ///             buf.try_extend_from_slice(field)?;
+    ///         }
+    ///         Ok(())
+    ///     })
+    /// }
+    ///
+    /// let mut buf = ArrayVec::new();
+    /// encode(&mut buf, &[b"a", b"b"]).unwrap();
+    /// assert!(encode(&mut buf, &[b"c", b"d"]).is_err());
+    /// assert_eq!(&buf[..], &[1, b'a', 1, b'b']);
+    /// ```
+    pub fn transaction<R, E, F>(&mut self, f: F) -> Result<R, E>
+        where F: FnOnce(&mut Self) -> Result<R, E>
+    {
+        let mut checkpoint = self.checkpoint();
+        let value = f(&mut checkpoint)?;
+        checkpoint.commit();
+        Ok(value)
     }
 
     /// Returns the ArrayVec, replacing the original with a new empty ArrayVec.
@@ -722,47 +2158,56 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     }
 
     /// Return a slice containing all elements of the vector.
-    pub fn as_slice(&self) -> &[T] {
-        ArrayVecImpl::as_slice(self)
+    pub const fn as_slice(&self) -> &[T] {
+        unsafe {
+            slice::from_raw_parts(self.as_ptr(), self.len())
+        }
     }
 
     /// Return a mutable slice containing all elements of the vector.
-    pub fn as_mut_slice(&mut self) -> &mut [T] {
-        ArrayVecImpl::as_mut_slice(self)
+    pub const fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = self.len();
+        unsafe {
+            slice::from_raw_parts_mut(self.as_mut_ptr(), len)
+        }
     }
 
     /// Return a raw pointer to the vector's buffer.
-    pub fn as_ptr(&self) -> *const T {
-        ArrayVecImpl::as_ptr(self)
+    pub const fn as_ptr(&self) -> *const T {
+        self.xs.as_ptr() as _
     }
 
     /// Return a raw mutable pointer to the vector's buffer.
-    pub fn as_mut_ptr(&mut self) -> *mut T {
-        ArrayVecImpl::as_mut_ptr(self)
+    pub const fn as_mut_ptr(&mut self) -> *mut T {
+        self.xs.as_mut_ptr() as _
     }
 }
 
-impl<T, const CAP: usize> ArrayVecImpl for ArrayVec<T, CAP> {
-    type Item = T;
-    const CAPACITY: usize = CAP;
-
-    fn len(&self) -> usize { self.len() }
-
-    unsafe fn set_len(&mut self, length: usize) {
-        debug_assert!(length <= CAP);
-        self.len = length as LenUint;
-    }
-
-    fn as_ptr(&self) -> *const Self::Item {
-        self.xs.as_ptr() as _
-    }
-
-    fn as_mut_ptr(&mut self) -> *mut Self::Item {
-        self.xs.as_mut_ptr() as _
+impl<A, B, const CAP: usize, L: LenType> ArrayVec<(A, B), CAP, L> {
+    /// Split a vector of pairs into two vectors with the same capacity.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let array = ArrayVec::from([(1, 'a'), (2, 'b')]);
+    /// let (numbers, letters) = array.unzip();
+    /// assert_eq!(&numbers[..], &[1, 2]);
+    /// assert_eq!(&letters[..], &['a', 'b']);
+    /// ```
+    pub fn unzip(self) -> (ArrayVec<A, CAP, L>, ArrayVec<B, CAP, L>) {
+        let mut left = ArrayVec::new();
+        let mut right = ArrayVec::new();
+        for (a, b) in self {
+            unsafe {
+                left.push_unchecked(a);
+                right.push_unchecked(b);
+            }
+        }
+        (left, right)
     }
 }
 
-impl<T, const CAP: usize> Deref for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> Deref for ArrayVec<T, CAP, L> {
     type Target = [T];
     #[inline]
     fn deref(&self) -> &Self::Target {
@@ -770,7 +2215,7 @@ impl<T, const CAP: usize> Deref for ArrayVec<T, CAP> {
     }
 }
 
-impl<T, const CAP: usize> DerefMut for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> DerefMut for ArrayVec<T, CAP, L> {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_mut_slice()
@@ -788,8 +2233,8 @@ impl<T, const CAP: usize> DerefMut for ArrayVec<T, CAP> {
 /// assert_eq!(array.capacity(), 3);
 /// ```
 impl<T, const CAP: usize> From<[T; CAP]> for ArrayVec<T, CAP> {
-    #[track_caller]
     fn from(array: [T; CAP]) -> Self {
+        let () = CapacityLimit::<CAP, LenUint>::CHECK;
         let array = ManuallyDrop::new(array);
         let mut vec = <ArrayVec<T, CAP>>::new();
         unsafe {
@@ -801,6 +2246,58 @@ impl<T, const CAP: usize> From<[T; CAP]> for ArrayVec<T, CAP> {
     }
 }
 
+/// Create an `ArrayVec` from a list of elements, like `vec!`.
+///
+/// - `arrayvec![CAP; a, b, c]` creates an `ArrayVec` with capacity `CAP`
+///   holding the given elements.
+/// - `arrayvec![CAP; elem; n]` creates an `ArrayVec` with capacity `CAP`
+///   holding `n` copies of `elem`; like an array expression, `elem` must be
+///   `Copy` or a constant and `n` must be a constant.
+/// - `arrayvec![a, b, c]` creates a full `ArrayVec` whose capacity is the
+///   number of elements.
+/// - `arrayvec![]` creates an empty `ArrayVec`, inferring the capacity.
+///
+/// More elements than `CAP` is a compile time error. The macro can be used
+/// in `const` and `static` items, and creates an `ArrayVec` with the default
+/// length type. It is built on [`ArrayVec::from_array_prefix`].
+///
+/// ```
+/// use arrayvec::{arrayvec, ArrayVec};
+///
+/// static DIGITS: ArrayVec<u8, 16> = arrayvec![16; 1, 2, 3];
+/// const ZEROS: ArrayVec<u8, 8> = arrayvec![8; 0; 4];
+///
+/// let names = arrayvec![String::from("a"), String::from("b")];
+/// let mut empty: ArrayVec<i32, 4> = arrayvec![];
+/// empty.push(1);
+///
+/// assert_eq!(&DIGITS[..], &[1, 2, 3]);
+/// assert_eq!(&ZEROS[..], &[0; 4]);
+/// assert_eq!(names.capacity(), 2);
+/// assert_eq!(&empty[..], &[1]);
+/// ```
+///
+/// ```compile_fail
+/// use arrayvec::arrayvec;
+///
+/// let digits = arrayvec![2; 1, 2, 3];
+/// ```
+#[macro_export]
+macro_rules! arrayvec {
+    () => {
+        $crate::ArrayVec::new_const()
+    };
+    ($cap:expr; $elem:expr; $n:expr) => {
+        $crate::ArrayVec::<_, { $cap }>::from_array_prefix([$elem; $n])
+    };
+    ($cap:expr; $($x:expr),* $(,)?) => {
+        $crate::ArrayVec::<_, { $cap }>::from_array_prefix([$($x),*])
+    };
+    ($($x:expr),+ $(,)?) => {
+        $crate::ArrayVec::<_, { <[&str]>::len(&[$(stringify!($x)),+]) }>::from_array_prefix([$($x),+])
+    };
+}
+
 
 /// Try to create an `ArrayVec` from a slice. This will return an error if the slice was too big to
 /// fit.
@@ -813,7 +2310,7 @@ impl<T, const CAP: usize> From<[T; CAP]> for ArrayVec<T, CAP> {
 /// assert_eq!(array.len(), 3);
 /// assert_eq!(array.capacity(), 4);
 /// ```
-impl<T, const CAP: usize> std::convert::TryFrom<&[T]> for ArrayVec<T, CAP>
+impl<T, const CAP: usize, L: LenType> std::convert::TryFrom<&[T]> for ArrayVec<T, CAP, L>
     where T: Clone,
 {
     type Error = CapacityError;
@@ -841,7 +2338,7 @@ impl<T, const CAP: usize> std::convert::TryFrom<&[T]> for ArrayVec<T, CAP>
 ///     // ...
 /// }
 /// ```
-impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a ArrayVec<T, CAP> {
+impl<'a, T: 'a, const CAP: usize, L: LenType> IntoIterator for &'a ArrayVec<T, CAP, L> {
     type Item = &'a T;
     type IntoIter = slice::Iter<'a, T>;
     fn into_iter(self) -> Self::IntoIter { self.iter() }
@@ -858,7 +2355,7 @@ impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a ArrayVec<T, CAP> {
 ///     // ...
 /// }
 /// ```
-impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a mut ArrayVec<T, CAP> {
+impl<'a, T: 'a, const CAP: usize, L: LenType> IntoIterator for &'a mut ArrayVec<T, CAP, L> {
     type Item = &'a mut T;
     type IntoIter = slice::IterMut<'a, T>;
     fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
@@ -875,10 +2372,10 @@ impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a mut ArrayVec<T, CAP> {
 ///     // ...
 /// }
 /// ```
-impl<T, const CAP: usize> IntoIterator for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> IntoIterator for ArrayVec<T, CAP, L> {
     type Item = T;
-    type IntoIter = IntoIter<T, CAP>;
-    fn into_iter(self) -> IntoIter<T, CAP> {
+    type IntoIter = IntoIter<T, CAP, L>;
+    fn into_iter(self) -> IntoIter<T, CAP, L> {
         IntoIter { index: 0, v: self, }
     }
 }
@@ -899,7 +2396,7 @@ impl<T, const CAP: usize> IntoIterator for ArrayVec<T, CAP> {
 /// let data = unsafe { core::slice::from_raw_parts(array.as_ptr(), array.capacity()) };
 /// assert_eq!(data, [0, 0, 0]);
 /// ```
-impl<Z: zeroize::Zeroize, const CAP: usize> zeroize::Zeroize for ArrayVec<Z, CAP> {
+impl<Z: zeroize::Zeroize, const CAP: usize, L: LenType> zeroize::Zeroize for ArrayVec<Z, CAP, L> {
     fn zeroize(&mut self) {
         // Zeroize all the contained elements.
         self.iter_mut().zeroize();
@@ -911,11 +2408,11 @@ impl<Z: zeroize::Zeroize, const CAP: usize> zeroize::Zeroize for ArrayVec<Z, CAP
 }
 
 /// By-value iterator for `ArrayVec`.
-pub struct IntoIter<T, const CAP: usize> {
+pub struct IntoIter<T, const CAP: usize, L: LenType = LenUint> {
     index: usize,
-    v: ArrayVec<T, CAP>,
+    v: ArrayVec<T, CAP, L>,
 }
-impl<T, const CAP: usize> IntoIter<T, CAP> {
+impl<T, const CAP: usize, L: LenType> IntoIter<T, CAP, L> {
     /// Returns the remaining items of this iterator as a slice.
     pub fn as_slice(&self) -> &[T] {
         &self.v[self.index..]
@@ -927,7 +2424,7 @@ impl<T, const CAP: usize> IntoIter<T, CAP> {
     }
 }
 
-impl<T, const CAP: usize> Iterator for IntoIter<T, CAP> {
+impl<T, const CAP: usize, L: LenType> Iterator for IntoIter<T, CAP, L> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -948,7 +2445,7 @@ impl<T, const CAP: usize> Iterator for IntoIter<T, CAP> {
     }
 }
 
-impl<T, const CAP: usize> DoubleEndedIterator for IntoIter<T, CAP> {
+impl<T, const CAP: usize, L: LenType> DoubleEndedIterator for IntoIter<T, CAP, L> {
     fn next_back(&mut self) -> Option<Self::Item> {
         if self.index == self.v.len() {
             None
@@ -962,9 +2459,9 @@ impl<T, const CAP: usize> DoubleEndedIterator for IntoIter<T, CAP> {
     }
 }
 
-impl<T, const CAP: usize> ExactSizeIterator for IntoIter<T, CAP> { }
+impl<T, const CAP: usize, L: LenType> ExactSizeIterator for IntoIter<T, CAP, L> { }
 
-impl<T, const CAP: usize> Drop for IntoIter<T, CAP> {
+impl<T, const CAP: usize, L: LenType> Drop for IntoIter<T, CAP, L> {
     fn drop(&mut self) {
         // panic safety: Set length to 0 before dropping elements.
         let index = self.index;
@@ -979,17 +2476,17 @@ impl<T, const CAP: usize> Drop for IntoIter<T, CAP> {
     }
 }
 
-impl<T, const CAP: usize> Clone for IntoIter<T, CAP>
+impl<T, const CAP: usize, L: LenType> Clone for IntoIter<T, CAP, L>
 where T: Clone,
 {
-    fn clone(&self) -> IntoIter<T, CAP> {
+    fn clone(&self) -> IntoIter<T, CAP, L> {
         let mut v = ArrayVec::new();
         v.extend_from_slice(&self.v[self.index..]);
         v.into_iter()
     }
 }
 
-impl<T, const CAP: usize> fmt::Debug for IntoIter<T, CAP>
+impl<T, const CAP: usize, L: LenType> fmt::Debug for IntoIter<T, CAP, L>
 where
     T: fmt::Debug,
 {
@@ -1001,20 +2498,20 @@ where
 }
 
 /// A draining iterator for `ArrayVec`.
-pub struct Drain<'a, T: 'a, const CAP: usize> {
+pub struct Drain<'a, T: 'a, const CAP: usize, L: LenType = LenUint> {
     /// Index of tail to preserve
     tail_start: usize,
     /// Length of tail
     tail_len: usize,
     /// Current remaining range to remove
     iter: slice::Iter<'a, T>,
-    vec: *mut ArrayVec<T, CAP>,
+    vec: *mut ArrayVec<T, CAP, L>,
 }
 
-unsafe impl<'a, T: Sync, const CAP: usize> Sync for Drain<'a, T, CAP> {}
-unsafe impl<'a, T: Send, const CAP: usize> Send for Drain<'a, T, CAP> {}
+unsafe impl<'a, T: Sync, const CAP: usize, L: LenType> Sync for Drain<'a, T, CAP, L> {}
+unsafe impl<'a, T: Send, const CAP: usize, L: LenType> Send for Drain<'a, T, CAP, L> {}
 
-impl<'a, T: 'a, const CAP: usize> Iterator for Drain<'a, T, CAP> {
+impl<'a, T: 'a, const CAP: usize, L: LenType> Iterator for Drain<'a, T, CAP, L> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -1030,7 +2527,7 @@ impl<'a, T: 'a, const CAP: usize> Iterator for Drain<'a, T, CAP> {
     }
 }
 
-impl<'a, T: 'a, const CAP: usize> DoubleEndedIterator for Drain<'a, T, CAP>
+impl<'a, T: 'a, const CAP: usize, L: LenType> DoubleEndedIterator for Drain<'a, T, CAP, L>
 {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.iter.next_back().map(|elt|
@@ -1041,9 +2538,9 @@ impl<'a, T: 'a, const CAP: usize> DoubleEndedIterator for Drain<'a, T, CAP>
     }
 }
 
-impl<'a, T: 'a, const CAP: usize> ExactSizeIterator for Drain<'a, T, CAP> {}
+impl<'a, T: 'a, const CAP: usize, L: LenType> ExactSizeIterator for Drain<'a, T, CAP, L> {}
 
-impl<'a, T: 'a, const CAP: usize> Drop for Drain<'a, T, CAP> {
+impl<'a, T: 'a, const CAP: usize, L: LenType> Drop for Drain<'a, T, CAP, L> {
     fn drop(&mut self) {
         // len is currently 0 so panicking while dropping will not cause a double drop.
 
@@ -1064,20 +2561,233 @@ impl<'a, T: 'a, const CAP: usize> Drop for Drain<'a, T, CAP> {
     }
 }
 
-/// Guard that writes a `usize` length back to a `LenUint` field on drop.
+impl<'a, T: 'a, const CAP: usize, L: LenType> Drain<'a, T, CAP, L> {
+    /// Fill the gap between the end of the vector and the tail with elements
+    /// from `replace_with`.
+    ///
+    /// Return `true` if the gap was filled, `false` if the iterator ran out.
+    ///
+    /// ## Safety
+    ///
+    /// The drained range must have been exhausted.
+    unsafe fn fill<I>(&mut self, replace_with: &mut I) -> bool
+        where I: Iterator<Item = T>
+    {
+        let vec = &mut *self.vec;
+        // The vector's length is updated for every element, so if
+        // `replace_with` panics, the drop of `self` moves the tail back
+        // behind the elements written so far.
+        while vec.len() < self.tail_start {
+            match replace_with.next() {
+                Some(elt) => vec.push_unchecked(elt),
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Move the tail to the end of the vector's buffer, to make the gap as
+    /// large as possible.
+    unsafe fn move_tail_to_end(&mut self) {
+        let vec = &mut *self.vec;
+        let new_tail_start = CAP - self.tail_len;
+        let ptr = vec.as_mut_ptr();
+        ptr::copy(ptr.add(self.tail_start), ptr.add(new_tail_start), self.tail_len);
+        self.tail_start = new_tail_start;
+    }
+}
+
+/// A splicing iterator for `ArrayVec`.
+///
+/// See [`ArrayVec::splice`] for details.
+pub struct Splice<'a, I: Iterator + 'a, const CAP: usize, L: LenType = LenUint> {
+    drain: Drain<'a, I::Item, CAP, L>,
+    replace_with: I,
+}
+
+impl<'a, I: Iterator, const CAP: usize, L: LenType> Iterator for Splice<'a, I, CAP, L> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.drain.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<'a, I: Iterator, const CAP: usize, L: LenType> DoubleEndedIterator for Splice<'a, I, CAP, L> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl<'a, I: Iterator, const CAP: usize, L: LenType> ExactSizeIterator for Splice<'a, I, CAP, L> {}
+
+impl<'a, I: Iterator, const CAP: usize, L: LenType> Drop for Splice<'a, I, CAP, L> {
+    fn drop(&mut self) {
+        // exhaust the drained range first
+        self.drain.by_ref().for_each(drop);
+
+        unsafe {
+            // Fill the gap left by the drained range; if there are more
+            // elements, move the tail out of the way and continue.
+            if !self.drain.fill(&mut self.replace_with) {
+                return;
+            }
+            self.drain.move_tail_to_end();
+            if !self.drain.fill(&mut self.replace_with) {
+                return;
+            }
+        }
+        if let Some(elt) = self.replace_with.next() {
+            drop(elt);
+            splice_panic();
+        }
+        // The drop of `self.drain` moves the tail back into place.
+    }
+}
+
+#[inline(never)]
+#[cold]
+fn splice_panic() {
+    panic!("ArrayVec: capacity exceeded in splice");
+}
+
+/// An iterator that removes the elements matching a predicate from an
+/// `ArrayVec`.
+///
+/// See [`ArrayVec::extract_if`] for details.
+pub struct ExtractIf<'a, T: 'a, F, const CAP: usize, L: LenType = LenUint> {
+    vec: &'a mut ArrayVec<T, CAP, L>,
+    /// Index of the next element to visit
+    idx: usize,
+    /// End of the range to visit
+    end: usize,
+    /// Number of elements removed so far
+    del: usize,
+    /// Length of the vector before extraction started
+    old_len: usize,
+    pred: F,
+}
+
+impl<'a, T: 'a, F, const CAP: usize, L: LenType> Iterator for ExtractIf<'a, T, F, CAP, L>
+    where F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.idx < self.end {
+            let i = self.idx;
+            let cur = unsafe { self.vec.as_mut_ptr().add(i) };
+            let extract = (self.pred)(unsafe { &mut *cur });
+            // Advance only after the predicate has returned, so that if it
+            // panics the current element is kept by the backshift in drop.
+            self.idx += 1;
+            if extract {
+                self.del += 1;
+                return Some(unsafe { ptr::read(cur) });
+            } else if self.del > 0 {
+                unsafe {
+                    ptr::copy_nonoverlapping(cur, cur.sub(self.del), 1);
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.idx))
+    }
+}
+
+impl<'a, T: 'a, F, const CAP: usize, L: LenType> Drop for ExtractIf<'a, T, F, CAP, L> {
+    fn drop(&mut self) {
+        // Backshift the elements that were not visited over the hole left by
+        // the removed ones, and restore the length.
+        if self.del > 0 {
+            unsafe {
+                let ptr = self.vec.as_mut_ptr();
+                ptr::copy(ptr.add(self.idx),
+                          ptr.add(self.idx - self.del),
+                          self.old_len - self.idx);
+            }
+        }
+        unsafe {
+            self.vec.set_len(self.old_len - self.del);
+        }
+    }
+}
+
+/// A guard that rolls an `ArrayVec` back to a saved length when dropped.
+///
+/// See [`ArrayVec::checkpoint`] for details.
+pub struct Checkpoint<'a, T, const CAP: usize, L: LenType = LenUint> {
+    vec: &'a mut ArrayVec<T, CAP, L>,
+    len: usize,
+}
+
+impl<'a, T, const CAP: usize, L: LenType> Checkpoint<'a, T, CAP, L> {
+    /// Return the saved length.
+    pub fn saved_len(&self) -> usize {
+        self.len
+    }
+
+    /// Keep the changes made since the checkpoint.
+    pub fn commit(self) {
+        mem::forget(self);
+    }
+
+    /// Roll back the changes made since the checkpoint, now.
+    pub fn rollback(self) {}
+}
+
+impl<'a, T, const CAP: usize, L: LenType> Deref for Checkpoint<'a, T, CAP, L> {
+    type Target = ArrayVec<T, CAP, L>;
+
+    fn deref(&self) -> &Self::Target {
+        self.vec
+    }
+}
+
+impl<'a, T, const CAP: usize, L: LenType> DerefMut for Checkpoint<'a, T, CAP, L> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.vec
+    }
+}
+
+impl<'a, T, const CAP: usize, L: LenType> Drop for Checkpoint<'a, T, CAP, L> {
+    fn drop(&mut self) {
+        self.vec.truncate(self.len);
+    }
+}
+
+impl<'a, T, const CAP: usize, L: LenType> fmt::Debug for Checkpoint<'a, T, CAP, L>
+    where T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Checkpoint")
+            .field("vec", &self.vec)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// Guard that writes a `usize` length back to a length field of type `L` on drop.
 ///
 /// Used to keep the `ArrayVec` length consistent if a panic occurs during
 /// element-by-element writing: on panic the vector is left with the temporally
 /// correct initialized length.
-struct WritebackGuard<'a> {
-    target: &'a mut LenUint,
+struct WritebackGuard<'a, L: LenType> {
+    target: &'a mut L,
     len: usize,
 }
 
-impl Drop for WritebackGuard<'_> {
+impl<L: LenType> Drop for WritebackGuard<'_, L> {
     #[inline(always)]
     fn drop(&mut self) {
-        *self.target = self.len as LenUint;
+        *self.target = len_from_usize(self.len);
     }
 }
 
@@ -1086,7 +2796,7 @@ impl Drop for WritebackGuard<'_> {
 /// Extend the `ArrayVec` with an iterator.
 /// 
 /// ***Panics*** if extending the vector exceeds its capacity.
-impl<T, const CAP: usize> Extend<T> for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> Extend<T> for ArrayVec<T, CAP, L> {
     /// Extend the `ArrayVec` with an iterator.
     /// 
     /// ***Panics*** if extending the vector exceeds its capacity.
@@ -1106,7 +2816,7 @@ fn extend_panic() {
 }
 
 
-impl<T, const CAP: usize> ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> ArrayVec<T, CAP, L> {
     /// Extend the vector from the iterator.
     ///
     /// ***Panics*** if extending the vector exceeds its capacity.
@@ -1115,7 +2825,7 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     ///
     /// If CHECK is false, the iterator must yield at most `CAP - len()` elements.
     #[track_caller]
-    unsafe fn extend_from_iter<I, const CHECK: bool>(&mut self, iter: I)
+    pub(crate) unsafe fn extend_from_iter<I, const CHECK: bool>(&mut self, iter: I)
         where I: Iterator<Item = T>
     {
         let len = self.len();
@@ -1156,7 +2866,7 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
 /// Create an `ArrayVec` from an iterator.
 /// 
 /// ***Panics*** if the number of elements in the iterator exceeds the arrayvec's capacity.
-impl<T, const CAP: usize> iter::FromIterator<T> for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> iter::FromIterator<T> for ArrayVec<T, CAP, L> {
     /// Create an `ArrayVec` from an iterator.
     /// 
     /// ***Panics*** if the number of elements in the iterator exceeds the arrayvec's capacity.
@@ -1167,7 +2877,7 @@ impl<T, const CAP: usize> iter::FromIterator<T> for ArrayVec<T, CAP> {
     }
 }
 
-impl<T, const CAP: usize> Clone for ArrayVec<T, CAP>
+impl<T, const CAP: usize, L: LenType> Clone for ArrayVec<T, CAP, L>
     where T: Clone
 {
     fn clone(&self) -> Self {
@@ -1229,7 +2939,7 @@ impl<T, const CAP: usize> Clone for ArrayVec<T, CAP>
     }
 }
 
-impl<T, const CAP: usize> Hash for ArrayVec<T, CAP>
+impl<T, const CAP: usize, L: LenType> Hash for ArrayVec<T, CAP, L>
     where T: Hash
 {
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -1237,7 +2947,7 @@ impl<T, const CAP: usize> Hash for ArrayVec<T, CAP>
     }
 }
 
-impl<T, const CAP: usize> PartialEq for ArrayVec<T, CAP>
+impl<T, const CAP: usize, L: LenType> PartialEq for ArrayVec<T, CAP, L>
     where T: PartialEq
 {
     fn eq(&self, other: &Self) -> bool {
@@ -1245,7 +2955,7 @@ impl<T, const CAP: usize> PartialEq for ArrayVec<T, CAP>
     }
 }
 
-impl<T, const CAP: usize> PartialEq<[T]> for ArrayVec<T, CAP>
+impl<T, const CAP: usize, L: LenType> PartialEq<[T]> for ArrayVec<T, CAP, L>
     where T: PartialEq
 {
     fn eq(&self, other: &[T]) -> bool {
@@ -1253,36 +2963,36 @@ impl<T, const CAP: usize> PartialEq<[T]> for ArrayVec<T, CAP>
     }
 }
 
-impl<T, const CAP: usize> Eq for ArrayVec<T, CAP> where T: Eq { }
+impl<T, const CAP: usize, L: LenType> Eq for ArrayVec<T, CAP, L> where T: Eq { }
 
-impl<T, const CAP: usize> Borrow<[T]> for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> Borrow<[T]> for ArrayVec<T, CAP, L> {
     fn borrow(&self) -> &[T] { self }
 }
 
-impl<T, const CAP: usize> BorrowMut<[T]> for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> BorrowMut<[T]> for ArrayVec<T, CAP, L> {
     fn borrow_mut(&mut self) -> &mut [T] { self }
 }
 
-impl<T, const CAP: usize> AsRef<[T]> for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> AsRef<[T]> for ArrayVec<T, CAP, L> {
     fn as_ref(&self) -> &[T] { self }
 }
 
-impl<T, const CAP: usize> AsMut<[T]> for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> AsMut<[T]> for ArrayVec<T, CAP, L> {
     fn as_mut(&mut self) -> &mut [T] { self }
 }
 
-impl<T, const CAP: usize> fmt::Debug for ArrayVec<T, CAP> where T: fmt::Debug {
+impl<T, const CAP: usize, L: LenType> fmt::Debug for ArrayVec<T, CAP, L> where T: fmt::Debug {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
 }
 
-impl<T, const CAP: usize> Default for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenType> Default for ArrayVec<T, CAP, L> {
     /// Return an empty array
-    fn default() -> ArrayVec<T, CAP> {
+    fn default() -> ArrayVec<T, CAP, L> {
         ArrayVec::new()
     }
 }
 
-impl<T, const CAP: usize> PartialOrd for ArrayVec<T, CAP> where T: PartialOrd {
+impl<T, const CAP: usize, L: LenType> PartialOrd for ArrayVec<T, CAP, L> where T: PartialOrd {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
         (**self).partial_cmp(other)
     }
@@ -1304,7 +3014,7 @@ impl<T, const CAP: usize> PartialOrd for ArrayVec<T, CAP> where T: PartialOrd {
     }
 }
 
-impl<T, const CAP: usize> Ord for ArrayVec<T, CAP> where T: Ord {
+impl<T, const CAP: usize, L: LenType> Ord for ArrayVec<T, CAP, L> where T: Ord {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
         (**self).cmp(other)
     }
@@ -1314,7 +3024,7 @@ impl<T, const CAP: usize> Ord for ArrayVec<T, CAP> where T: Ord {
 /// `Write` appends written data to the end of the vector.
 ///
 /// Requires `features="std"`.
-impl<const CAP: usize> io::Write for ArrayVec<u8, CAP> {
+impl<const CAP: usize, L: LenType> io::Write for ArrayVec<u8, CAP, L> {
     fn write(&mut self, data: &[u8]) -> io::Result<usize> {
         let len = cmp::min(self.remaining_capacity(), data.len());
         let _result = self.try_extend_from_slice(&data[..len]);
@@ -1326,7 +3036,7 @@ impl<const CAP: usize> io::Write for ArrayVec<u8, CAP> {
 
 #[cfg(feature="serde")]
 /// Requires crate feature `"serde"`
-impl<T: Serialize, const CAP: usize> Serialize for ArrayVec<T, CAP> {
+impl<T: Serialize, const CAP: usize, L: LenType> Serialize for ArrayVec<T, CAP, L> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer
     {
@@ -1336,17 +3046,17 @@ impl<T: Serialize, const CAP: usize> Serialize for ArrayVec<T, CAP> {
 
 #[cfg(feature="serde")]
 /// Requires crate feature `"serde"`
-impl<'de, T: Deserialize<'de>, const CAP: usize> Deserialize<'de> for ArrayVec<T, CAP> {
+impl<'de, T: Deserialize<'de>, const CAP: usize, L: LenType> Deserialize<'de> for ArrayVec<T, CAP, L> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: Deserializer<'de>
     {
         use serde::de::{Visitor, SeqAccess, Error};
         use std::marker::PhantomData;
 
-        struct ArrayVecVisitor<'de, T: Deserialize<'de>, const CAP: usize>(PhantomData<(&'de (), [T; CAP])>);
+        struct ArrayVecVisitor<'de, T: Deserialize<'de>, const CAP: usize, L: LenType>(PhantomData<(&'de (), [T; CAP], L)>);
 
-        impl<'de, T: Deserialize<'de>, const CAP: usize> Visitor<'de> for ArrayVecVisitor<'de, T, CAP> {
-            type Value = ArrayVec<T, CAP>;
+        impl<'de, T: Deserialize<'de>, const CAP: usize, L: LenType> Visitor<'de> for ArrayVecVisitor<'de, T, CAP, L> {
+            type Value = ArrayVec<T, CAP, L>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 write!(formatter, "an array with no more than {} items", CAP)
@@ -1355,7 +3065,7 @@ impl<'de, T: Deserialize<'de>, const CAP: usize> Deserialize<'de> for ArrayVec<T
             fn visit_seq<SA>(self, mut seq: SA) -> Result<Self::Value, SA::Error>
                 where SA: SeqAccess<'de>,
             {
-                let mut values = ArrayVec::<T, CAP>::new();
+                let mut values = ArrayVec::<T, CAP, L>::new();
 
                 while let Some(value) = seq.next_element()? {
                     if let Err(_) = values.try_push(value) {
@@ -1367,13 +3077,13 @@ impl<'de, T: Deserialize<'de>, const CAP: usize> Deserialize<'de> for ArrayVec<T
             }
         }
 
-        deserializer.deserialize_seq(ArrayVecVisitor::<T, CAP>(PhantomData))
+        deserializer.deserialize_seq(ArrayVecVisitor::<T, CAP, L>(PhantomData))
     }
 }
 
 #[cfg(feature = "borsh")]
 /// Requires crate feature `"borsh"`
-impl<T, const CAP: usize> borsh::BorshSerialize for ArrayVec<T, CAP>
+impl<T, const CAP: usize, L: LenType> borsh::BorshSerialize for ArrayVec<T, CAP, L>
 where
     T: borsh::BorshSerialize,
 {
@@ -1384,7 +3094,7 @@ where
 
 #[cfg(feature = "borsh")]
 /// Requires crate feature `"borsh"`
-impl<T, const CAP: usize> borsh::BorshDeserialize for ArrayVec<T, CAP>
+impl<T, const CAP: usize, L: LenType> borsh::BorshDeserialize for ArrayVec<T, CAP, L>
 where
     T: borsh::BorshDeserialize,
 {
@@ -47,3 +47,57 @@ impl<T> fmt::Debug for CapacityError<T> {
     }
 }
 
+
+/// Error value indicating that formatted output does not fit in an
+/// `ArrayString`, or that a formatting trait implementation returned an
+/// error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FormatError {
+    required: usize,
+    available: usize,
+    fmt_error: bool,
+}
+
+impl FormatError {
+    pub(crate) fn new(required: usize, available: usize) -> FormatError {
+        FormatError { required, available, fmt_error: false }
+    }
+
+    pub(crate) fn from_fmt_error(written: usize, available: usize) -> FormatError {
+        FormatError { required: written, available, fmt_error: true }
+    }
+
+    /// Return the length in bytes that the formatted output needs.
+    ///
+    /// If a formatting trait implementation returned an error, this is the
+    /// length of the output up to that point.
+    pub fn required(&self) -> usize {
+        self.required
+    }
+
+    /// Return the length in bytes that was available for it.
+    pub fn available(&self) -> usize {
+        self.available
+    }
+
+    /// Return `true` if a formatting trait implementation returned an error,
+    /// rather than the output not fitting.
+    pub fn is_fmt_error(&self) -> bool {
+        self.fmt_error
+    }
+}
+
+#[cfg(feature="std")]
+/// Requires `features="std"`.
+impl Error for FormatError {}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.fmt_error {
+            write!(f, "a formatting trait implementation returned an error")
+        } else {
+            write!(f, "{}: formatted output needs {} bytes, but {} are available",
+                   CAPERROR, self.required, self.available)
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{ArrayString, ArrayVec};
+
+/// Extension methods for iterators, to collect them into fixed capacity
+/// collections without panicking, or to split them into batches.
+///
+/// This trait is implemented for all iterators.
+pub trait IteratorExt: Iterator {
+    /// Collect the elements into an `ArrayVec`.
+    ///
+    /// Returns an error if there are more than `CAP` elements; see
+    /// [`ArrayVec::try_from_iter`] for details.
+    ///
+    /// ```
+    /// use arrayvec::IteratorExt;
+    ///
+    /// let array = (0..3).map(|i| i * 2).try_collect_arrayvec::<4>().unwrap();
+    /// assert_eq!(&array[..], &[0, 2, 4]);
+    /// assert!((0..5).try_collect_arrayvec::<4>().is_err());
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn try_collect_arrayvec<const CAP: usize>(self)
+        -> Result<ArrayVec<Self::Item, CAP>, (ArrayVec<Self::Item, CAP>, Self::Item, Self)>
+        where Self: Sized
+    {
+        ArrayVec::try_from_iter(self)
+    }
+
+    /// Collect the characters into an `ArrayString`.
+    ///
+    /// Returns an error if the characters do not fit; see
+    /// [`ArrayString::try_from_chars`] for details.
+    ///
+    /// ```
+    /// use arrayvec::IteratorExt;
+    ///
+    /// let string = "hello".chars().rev().try_collect_arraystring::<8>().unwrap();
+    /// assert_eq!(&string[..], "olleh");
+    /// assert!("hello".chars().try_collect_arraystring::<4>().is_err());
+    /// ```
+    fn try_collect_arraystring<const CAP: usize>(self)
+        -> Result<ArrayString<CAP>, (ArrayString<CAP>, char, Self)>
+        where Self: Sized + Iterator<Item = char>
+    {
+        ArrayString::try_from_chars(self)
+    }
+
+    /// Concatenate the string slices into an `ArrayString`.
+    ///
+    /// Returns an error if the string slices do not fit; see
+    /// [`ArrayString::try_from_strs`] for details.
+    ///
+    /// ```
+    /// use arrayvec::IteratorExt;
+    ///
+    /// let string = "a b c".split(' ').try_concat_arraystring::<8>().unwrap();
+    /// assert_eq!(&string[..], "abc");
+    /// assert!("a b c".split(' ').try_concat_arraystring::<2>().is_err());
+    /// ```
+    fn try_concat_arraystring<'a, const CAP: usize>(self)
+        -> Result<ArrayString<CAP>, (ArrayString<CAP>, &'a str, Self)>
+        where Self: Sized + Iterator<Item = &'a str>
+    {
+        ArrayString::try_from_strs(self)
+    }
+
+    /// Split the iterator into batches of `N` elements; see [`chunks`].
+    ///
+    /// ```
+    /// use arrayvec::IteratorExt;
+    ///
+    /// let mut batches = (0..5).arrayvec_chunks::<2>();
+    /// assert_eq!(&batches.next().unwrap()[..], &[0, 1]);
+    /// assert_eq!(&batches.next().unwrap()[..], &[2, 3]);
+    /// assert_eq!(&batches.next().unwrap()[..], &[4]);
+    /// assert!(batches.next().is_none());
+    /// ```
+    #[track_caller]
+    fn arrayvec_chunks<const N: usize>(self) -> Chunks<Self, N>
+        where Self: Sized
+    {
+        chunks(self)
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+/// Create an iterator that yields the elements of `iter` in batches of `N`
+/// elements, as `ArrayVec<T, N>` values. The last batch may be shorter.
+///
+/// ***Panics*** if `N` is zero.
+///
+/// ```
+/// use arrayvec::chunks;
+///
+/// let sums: Vec<i32> = chunks::<_, 3>(1..=7).map(|batch| batch.iter().sum()).collect();
+/// assert_eq!(sums, [6, 15, 7]);
+/// ```
+#[track_caller]
+pub fn chunks<I, const N: usize>(iter: I) -> Chunks<I::IntoIter, N>
+    where I: IntoIterator
+{
+    assert!(N != 0, "chunks: batch size must be non-zero");
+    Chunks { iter: iter.into_iter() }
+}
+
+/// An iterator that yields batches of elements as `ArrayVec`s.
+///
+/// See [`chunks`] for details.
+#[derive(Clone, Debug)]
+pub struct Chunks<I, const N: usize> {
+    iter: I,
+}
+
+impl<I: Iterator, const N: usize> Iterator for Chunks<I, N> {
+    type Item = ArrayVec<I::Item, N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut batch = ArrayVec::new();
+        unsafe {
+            batch.extend_from_iter::<_, false>(self.iter.by_ref().take(N));
+        }
+        if batch.is_empty() {
+            None
+        } else {
+            Some(batch)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        let batches = |n: usize| n / N + (n % N != 0) as usize;
+        (batches(lo), hi.map(batches))
+    }
+}
@@ -0,0 +1,95 @@
+use std::mem::{self, MaybeUninit};
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+    impl Sealed for usize {}
+}
+
+/// An unsigned integer type that stores the length of an `ArrayVec` or
+/// `ArrayString`.
+///
+/// The length type is the last type parameter of the collections, and the
+/// capacity must not be larger than its `MAX`. A smaller length type makes
+/// small collections more compact, while `usize` allows the largest
+/// capacity. Using a capacity that does not fit the length type is a compile
+/// time error.
+///
+/// This trait is sealed and implemented for `u8`, `u16`, `u32` and `usize`.
+///
+/// ```
+/// use arrayvec::ArrayVec;
+/// use std::mem::size_of;
+///
+/// let mut packet = ArrayVec::<u8, 15, u8>::new();
+/// packet.push(1);
+/// assert_eq!(size_of::<ArrayVec<u8, 15, u8>>(), 16);
+/// ```
+///
+/// ```compile_fail
+/// use arrayvec::ArrayVec;
+///
+/// let packet = ArrayVec::<u8, 256, u8>::new();
+/// ```
+pub trait LenType: private::Sealed + Copy {
+    /// The largest length that this type can represent.
+    const MAX: usize;
+}
+
+impl LenType for u8 {
+    const MAX: usize = u8::MAX as usize;
+}
+
+impl LenType for u16 {
+    const MAX: usize = u16::MAX as usize;
+}
+
+impl LenType for u32 {
+    const MAX: usize = if mem::size_of::<u32>() > mem::size_of::<usize>() {
+        usize::MAX
+    } else {
+        u32::MAX as usize
+    };
+}
+
+impl LenType for usize {
+    const MAX: usize = usize::MAX;
+}
+
+// The conversions below are `const fn`, which trait methods can not be. They
+// pick the integer type by size, which is unambiguous for the sealed set of
+// implementations.
+
+/// Convert the length `len` to `usize`.
+#[inline(always)]
+pub(crate) const fn len_to_usize<L: LenType>(len: L) -> usize {
+    let ptr = &len as *const L;
+    unsafe {
+        match mem::size_of::<L>() {
+            1 => *(ptr as *const u8) as usize,
+            2 => *(ptr as *const u16) as usize,
+            4 => *(ptr as *const u32) as usize,
+            _ => *(ptr as *const usize),
+        }
+    }
+}
+
+/// Convert `n` to a length; `n` must not be greater than `L::MAX`.
+#[inline(always)]
+pub(crate) const fn len_from_usize<L: LenType>(n: usize) -> L {
+    debug_assert!(n <= L::MAX);
+    let mut len = MaybeUninit::<L>::uninit();
+    let ptr = len.as_mut_ptr();
+    unsafe {
+        match mem::size_of::<L>() {
+            1 => *(ptr as *mut u8) = n as u8,
+            2 => *(ptr as *mut u16) = n as u16,
+            4 => *(ptr as *mut u32) = n as u32,
+            _ => *(ptr as *mut usize) = n,
+        }
+        len.assume_init()
+    }
+}
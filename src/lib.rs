@@ -1,5 +1,6 @@
-//! **arrayvec** provides the types [`ArrayVec`] and [`ArrayString`]: 
-//! array-backed vector and string types, which store their contents inline.
+//! **arrayvec** provides the types [`ArrayVec`], [`ArrayString`] and [`ArrayDeque`]:
+//! array-backed vector, string and double-ended queue types, which store their
+//! contents inline.
 //!
 //! The arrayvec package has the following cargo features:
 //!
@@ -9,15 +10,15 @@
 //!
 //! - `serde`
 //!   - Optional
-//!   - Enable serialization for ArrayVec and ArrayString using serde 1.x
+//!   - Enable serialization for ArrayVec, ArrayString and ArrayDeque using serde 1.x
 //!
 //! - `zeroize`
 //!   - Optional
-//!   - Implement `Zeroize` for ArrayVec and ArrayString
+//!   - Implement `Zeroize` for ArrayVec, ArrayString and ArrayDeque
 //!
 //! ## Rust Version
 //!
-//! This version of arrayvec requires Rust 1.51 or later.
+//! This version of arrayvec requires Rust 1.83 or later.
 //!
 #![doc(html_root_url="https://docs.rs/arrayvec/0.7/")]
 #![cfg_attr(not(feature="std"), no_std)]
@@ -34,37 +35,25 @@ pub(crate) type LenUint = u32;
 #[cfg(target_pointer_width = "16")]
 pub(crate) type LenUint = u16;
 
-macro_rules! assert_capacity_limit {
-    ($cap:expr) => {
-        if std::mem::size_of::<usize>() > std::mem::size_of::<LenUint>() {
-            if $cap > LenUint::MAX as usize {
-                #[cfg(not(target_pointer_width = "16"))]
-                panic!("ArrayVec: largest supported capacity is u32::MAX");
-                #[cfg(target_pointer_width = "16")]
-                panic!("ArrayVec: largest supported capacity is u16::MAX");
-            }
-        }
-    }
-}
-
-macro_rules! assert_capacity_limit_const {
-    ($cap:expr) => {
-        if std::mem::size_of::<usize>() > std::mem::size_of::<LenUint>() {
-            if $cap > LenUint::MAX as usize {
-                [/*ArrayVec: largest supported capacity is u32::MAX*/][$cap]
-            }
-        }
-    }
-}
-
-mod arrayvec_impl;
 mod arrayvec;
 mod array_string;
+mod array_deque;
 mod char;
 mod errors;
+mod iter_ext;
+mod len_type;
 mod utils;
 
-pub use crate::array_string::ArrayString;
-pub use crate::errors::CapacityError;
-
-pub use crate::arrayvec::{ArrayVec, IntoIter, Drain};
+pub use crate::array_string::{ArrayString, StringCheckpoint, TruncatingWriter};
+pub use crate::array_deque::{
+    ArrayDeque,
+    Drain as DequeDrain,
+    IntoIter as DequeIntoIter,
+    Iter as DequeIter,
+    IterMut as DequeIterMut,
+};
+pub use crate::errors::{CapacityError, FormatError};
+pub use crate::iter_ext::{chunks, Chunks, IteratorExt};
+pub use crate::len_type::LenType;
+
+pub use crate::arrayvec::{ArrayVec, IntoIter, Drain, Splice, ExtractIf, Checkpoint};
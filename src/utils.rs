@@ -1,5 +1,8 @@
 use std::marker::PhantomData;
-use std::mem::MaybeUninit;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ops::{Bound, RangeBounds};
+
+use crate::LenType;
 
 pub(crate) struct MakeMaybeUninit<T, const N: usize>(PhantomData<fn() -> T>);
 
@@ -9,3 +12,52 @@ impl<T, const N: usize> MakeMaybeUninit<T, N> {
     pub(crate) const ARRAY: [MaybeUninit<T>; N] = [Self::VALUE; N];
 }
 
+/// Compile time check that the capacity `CAP` is supported, which means that
+/// it fits in the length type `L`.
+///
+/// Every constructor uses `CHECK`, so that creating a collection with a
+/// larger capacity fails the build.
+pub(crate) struct CapacityLimit<const CAP: usize, L>(PhantomData<L>);
+
+impl<const CAP: usize, L: LenType> CapacityLimit<CAP, L> {
+    pub(crate) const CHECK: () = assert!(CAP <= L::MAX,
+                                         "capacity is too large for the length type");
+}
+
+/// Compile time check that a capacity of `FROM` fits in a capacity of `TO`.
+///
+/// Using `FITS` in a function fails the build when the function is
+/// instantiated with `FROM > TO`.
+pub(crate) struct CapacityFits<const FROM: usize, const TO: usize>;
+
+impl<const FROM: usize, const TO: usize> CapacityFits<FROM, TO> {
+    pub(crate) const FITS: () = assert!(FROM <= TO, "capacity is too small");
+}
+
+/// Reinterpret an array of `N` elements as the first `N` elements of an
+/// array of `CAP` possibly uninitialized elements, which requires `N <= CAP`.
+#[repr(C)]
+pub(crate) union ArrayPrefix<T, const N: usize, const CAP: usize> {
+    pub(crate) array: ManuallyDrop<[T; N]>,
+    pub(crate) xs: ManuallyDrop<[MaybeUninit<T>; CAP]>,
+}
+
+/// Convert `range` to a `(start, end)` pair of indices, using `len` for an
+/// unbounded end.
+///
+/// The result is not bounds checked.
+pub(crate) fn range_to_indices<R>(range: &R, len: usize) -> (usize, usize)
+    where R: RangeBounds<usize>
+{
+    let start = match range.start_bound() {
+        Bound::Unbounded => 0,
+        Bound::Included(&i) => i,
+        Bound::Excluded(&i) => i.saturating_add(1),
+    };
+    let end = match range.end_bound() {
+        Bound::Excluded(&j) => j,
+        Bound::Included(&j) => j.saturating_add(1),
+        Bound::Unbounded => len,
+    };
+    (start, end)
+}
@@ -46,6 +46,36 @@ mod array_vec {
     }
 }
 
+mod array_deque {
+    use arrayvec::ArrayDeque;
+    use super::{assert_ser, assert_roundtrip};
+
+    #[test]
+    fn test_empty() {
+        let deque = ArrayDeque::<u32, 0>::new();
+        assert_ser(&deque, b"\0\0\0\0");
+        assert_roundtrip(&deque);
+    }
+
+    #[test]
+    fn test_full() {
+        let mut deque = ArrayDeque::<u32, 3>::new();
+        deque.push_back(0x123);
+        deque.push_back(0x456);
+        deque.push_front(0xdeadbeef);
+        assert_ser(&deque, b"\x03\0\0\0\xef\xbe\xad\xde\x23\x01\0\0\x56\x04\0\0");
+        assert_roundtrip(&deque);
+    }
+
+    #[test]
+    fn test_with_free_capacity() {
+        let mut deque = ArrayDeque::<u32, 3>::new();
+        deque.push_front(0xdeadbeef);
+        assert_ser(&deque, b"\x01\0\0\0\xef\xbe\xad\xde");
+        assert_roundtrip(&deque);
+    }
+}
+
 mod array_string {
     use arrayvec::ArrayString;
     use super::{assert_ser, assert_roundtrip};
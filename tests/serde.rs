@@ -45,6 +45,49 @@ mod array_vec {
     }
 }
 
+mod array_deque {
+    use arrayvec::ArrayDeque;
+
+    use serde_test::{Token, assert_tokens, assert_de_tokens_error};
+
+    #[test]
+    fn test_ser_de_empty() {
+        let deque = ArrayDeque::<u32, 0>::new();
+
+        assert_tokens(&deque, &[
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+        ]);
+    }
+
+
+    #[test]
+    fn test_ser_de() {
+        let mut deque = ArrayDeque::<u32, 3>::new();
+        deque.push_back(55);
+        deque.push_back(123);
+        deque.push_front(20);
+
+        assert_tokens(&deque, &[
+            Token::Seq { len: Some(3) },
+            Token::U32(20),
+            Token::U32(55),
+            Token::U32(123),
+            Token::SeqEnd,
+        ]);
+    }
+
+    #[test]
+    fn test_de_too_large() {
+        assert_de_tokens_error::<ArrayDeque<u32, 2>>(&[
+            Token::Seq { len: Some(3) },
+            Token::U32(13),
+            Token::U32(42),
+            Token::U32(68),
+        ], "invalid length 3, expected an array with no more than 2 items");
+    }
+}
+
 mod array_string {
     use arrayvec::ArrayString;
 
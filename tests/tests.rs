@@ -3,6 +3,7 @@ extern crate arrayvec;
 
 use arrayvec::ArrayVec;
 use arrayvec::ArrayString;
+use arrayvec::ArrayDeque;
 use std::mem;
 use arrayvec::CapacityError;
 
@@ -392,6 +393,1001 @@ fn test_drain_range_inclusive_oob() {
     v.drain(0..=0);
 }
 
+#[test]
+fn test_splice() {
+    let mut v = ArrayVec::<_, 8>::new();
+    v.extend(0..6);
+    let removed: Vec<_> = v.splice(1..3, 10..13).collect();
+    assert_eq!(removed, [1, 2]);
+    assert_eq!(&v[..], &[0, 10, 11, 12, 3, 4, 5]);
+
+    // shrink
+    v.splice(1..5, Some(20));
+    assert_eq!(&v[..], &[0, 20, 4, 5]);
+
+    // grow up to exactly the capacity, not consuming the splice
+    v.splice(2..2, 30..34);
+    assert_eq!(&v[..], &[0, 20, 30, 31, 32, 33, 4, 5]);
+
+    // replace the tail entirely
+    v.splice(4.., 40..42);
+    assert_eq!(&v[..], &[0, 20, 30, 31, 40, 41]);
+
+    let mut v = ArrayVec::from([0, 1, 2]);
+    v.splice(..2, None);
+    assert_eq!(&v[..], &[2]);
+}
+
+#[test]
+#[should_panic(expected="capacity exceeded")]
+fn test_splice_overflow() {
+    let mut v = ArrayVec::from([0, 1, 2]);
+    v.splice(1..2, 10..12);
+}
+
+#[test]
+fn test_try_splice() {
+    let mut v = ArrayVec::<_, 5>::new();
+    v.extend(0..4);
+    let err = v.try_splice(1..2, vec![7, 8, 9]).err().unwrap();
+    assert_eq!(err.element().collect::<Vec<_>>(), [7, 8, 9]);
+    assert_eq!(&v[..], &[0, 1, 2, 3]);
+
+    let removed: Vec<_> = v.try_splice(..2, vec![7, 8, 9]).unwrap().collect();
+    assert_eq!(removed, [0, 1]);
+    assert_eq!(&v[..], &[7, 8, 9, 2, 3]);
+}
+
+#[test]
+fn test_splice_panic_safety() {
+    use std::cell::Cell;
+    use std::panic::catch_unwind;
+    use std::panic::AssertUnwindSafe;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let mut v = ArrayVec::<_, 8>::new();
+    for _ in 0..6 {
+        v.push(Bump(flag));
+    }
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        let replacement = (0..5).map(|i| {
+            if i == 3 {
+                panic!("panic in replacement iterator");
+            }
+            Bump(flag)
+        });
+        v.splice(1..2, replacement);
+    }));
+    assert!(res.is_err());
+    // the drained element was dropped and three replacements were inserted
+    assert_eq!(flag.get(), 1);
+    assert_eq!(v.len(), 8);
+    drop(v);
+    assert_eq!(flag.get(), 9);
+}
+
+#[test]
+fn test_arrayvec_macro() {
+    use arrayvec::arrayvec;
+
+    const TABLE: ArrayVec<(u8, char), 4> = arrayvec![4; (1, 'a'), (2, 'b')];
+    static EMPTY: ArrayVec<u8, 4> = arrayvec![4;];
+    assert_eq!(&TABLE[..], &[(1, 'a'), (2, 'b')]);
+    assert!(EMPTY.is_empty());
+
+    let v = arrayvec![3; 7; 2];
+    assert_eq!(&v[..], &[7, 7]);
+    assert_eq!(v.capacity(), 3);
+    let v = arrayvec!["x".to_string(), "y".to_string(), "z".to_string(),];
+    assert_eq!(&v[..], &["x", "y", "z"]);
+    assert!(v.is_full());
+    let v: ArrayVec<Vec<i32>, 2> = arrayvec![];
+    assert!(v.is_empty());
+}
+
+#[test]
+fn test_from_array_prefix_drop() {
+    use std::cell::Cell;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let v = ArrayVec::<_, 5>::from_array_prefix([Bump(flag), Bump(flag), Bump(flag)]);
+    assert_eq!(v.len(), 3);
+    assert_eq!(flag.get(), 0);
+    drop(v);
+    assert_eq!(flag.get(), 3);
+}
+
+#[test]
+fn test_const_fn() {
+    const fn squares<const N: usize>() -> ArrayVec<u32, N> {
+        let mut v = ArrayVec::new_const();
+        while !v.is_full() {
+            let i = v.len() as u32;
+            v.push_const(i * i);
+        }
+        v
+    }
+
+    static SQUARES: ArrayVec<u32, 5> = squares();
+    const POPPED: (ArrayVec<u32, 5>, Option<u32>) = {
+        let mut v = squares::<5>();
+        let last = v.pop();
+        v.truncate_const(3);
+        assert!(v.try_push(10).is_ok());
+        (v, last)
+    };
+    const SECOND: u32 = SQUARES.as_slice()[2];
+    assert_eq!(&SQUARES[..], &[0, 1, 4, 9, 16]);
+    assert_eq!(&POPPED.0[..], &[0, 1, 4, 10]);
+    assert_eq!(POPPED.1, Some(16));
+    assert_eq!(SECOND, 4);
+
+    const GREETING: ArrayString<8> = {
+        let mut s = ArrayString::new_const();
+        s.push_str("hello");
+        assert!(s.try_push_str(", world").is_err());
+        s
+    };
+    const LEN: usize = GREETING.as_str().len();
+    assert_eq!(&GREETING[..], "hello");
+    assert_eq!(LEN, 5);
+}
+
+#[test]
+#[should_panic(expected="insufficient capacity")]
+fn test_push_overflow() {
+    let mut v = ArrayVec::<String, 1>::new();
+    v.push("a".to_string());
+    v.push("b".to_string());
+}
+
+#[test]
+fn test_push_overflow_drop() {
+    use std::cell::Cell;
+    use std::panic::catch_unwind;
+    use std::panic::AssertUnwindSafe;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let mut v = ArrayVec::<_, 1>::new();
+    v.push(Bump(flag));
+    let res = catch_unwind(AssertUnwindSafe(|| v.push(Bump(flag))));
+    assert!(res.is_err());
+    // the element that did not fit was dropped, not leaked
+    assert_eq!(flag.get(), 1);
+    drop(v);
+    assert_eq!(flag.get(), 2);
+}
+
+#[test]
+#[should_panic(expected="insufficient capacity")]
+fn test_push_const_overflow() {
+    let mut v = ArrayVec::<u8, 0>::new();
+    v.push_const(1);
+}
+
+#[test]
+fn test_from_fn() {
+    let v = ArrayVec::<_, 4>::from_fn(4, |i| i.to_string());
+    assert_eq!(&v[..], &["0", "1", "2", "3"]);
+    let v = ArrayVec::<String, 4>::from_fn(0, |_| unreachable!());
+    assert!(v.is_empty());
+
+    let res = ArrayVec::<_, 4>::try_from_fn(3, |i| if i < 5 { Ok(i) } else { Err(i) });
+    assert_eq!(&res.unwrap()[..], &[0, 1, 2]);
+}
+
+#[test]
+#[should_panic(expected="exceeds capacity")]
+fn test_from_fn_overflow() {
+    ArrayVec::<_, 4>::from_fn(5, |i| i);
+}
+
+#[test]
+fn test_try_from_fn_drop() {
+    use std::cell::Cell;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let res = ArrayVec::<_, 8>::try_from_fn(6, |i| if i < 4 { Ok(Bump(flag)) } else { Err(i) });
+    assert_eq!(res.err(), Some(4));
+    assert_eq!(flag.get(), 4);
+}
+
+#[test]
+fn test_map_zip() {
+    let v = ArrayVec::<_, 4>::from_fn(3, |i| i);
+    let v = v.map(|x| x.to_string());
+    assert_eq!(&v[..], &["0", "1", "2"]);
+    assert_eq!(v.capacity(), 4);
+
+    let v = v.try_map(|s| s.parse::<u8>()).unwrap();
+    assert_eq!(&v[..], &[0, 1, 2]);
+    let none = v.clone().try_map(|x| x.checked_sub(1).ok_or(()));
+    assert_eq!(none, Err(()));
+
+    let pairs = v.zip(vec!['a', 'b', 'c', 'd']);
+    assert_eq!(&pairs[..], &[(0, 'a'), (1, 'b'), (2, 'c')]);
+}
+
+#[test]
+fn test_partition_unzip() {
+    let v = ArrayVec::<_, 6>::from_fn(6, |i| i.to_string());
+    let (short, long) = v.partition(|s| s.as_str() < "3");
+    assert_eq!(&short[..], &["0", "1", "2"]);
+    assert_eq!(&long[..], &["3", "4", "5"]);
+    assert_eq!(short.capacity(), 6);
+
+    let pairs = short.zip(long);
+    let (a, b) = pairs.unzip();
+    assert_eq!(&a[..], &["0", "1", "2"]);
+    assert_eq!(&b[..], &["3", "4", "5"]);
+
+    let mut left = ArrayVec::<_, 2>::new();
+    let mut right = ArrayVec::<_, 8>::new();
+    let v = ArrayVec::from([0, 1, 2, 3, 4, 5]);
+    let rest = v.try_partition_into(&mut left, &mut right, |x| *x % 2 == 0).unwrap_err();
+    assert_eq!(&left[..], &[0, 2]);
+    assert_eq!(&right[..], &[1, 3]);
+    assert_eq!(&rest.element()[..], &[4, 5]);
+    ArrayVec::from([7, 9]).try_partition_into(&mut left, &mut right, |x| *x % 2 == 0).unwrap();
+    assert_eq!(&right[..], &[1, 3, 7, 9]);
+}
+
+#[test]
+fn test_try_map_drop() {
+    use std::cell::Cell;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let v = ArrayVec::<_, 8>::from_fn(6, |i| i);
+    let res = v.try_map(|i| if i < 4 { Ok(Bump(flag)) } else { Err(i) });
+    assert_eq!(res.err(), Some(4));
+    assert_eq!(flag.get(), 4);
+
+    flag.set(0);
+    let v = ArrayVec::<_, 8>::from_fn(6, |_| Bump(flag));
+    let res = v.try_map(|b| { drop(b); Err::<(), _>(()) });
+    assert!(res.is_err());
+    assert_eq!(flag.get(), 6);
+
+    flag.set(0);
+    let v = ArrayVec::<_, 8>::from_fn(6, |i| i);
+    let res = v.try_map_opt(|i| if i < 3 { Some(Bump(flag)) } else { None });
+    assert!(res.is_none());
+    assert_eq!(flag.get(), 3);
+    let v = ArrayVec::<_, 8>::from_fn(6, |i| i);
+    assert_eq!(v.try_map_opt(|i| i.checked_sub(0)).unwrap().len(), 6);
+}
+
+#[test]
+fn test_chunks() {
+    use arrayvec::IteratorExt;
+
+    let batches: Vec<_> = arrayvec::chunks::<_, 4>((0..8).map(|i| i.to_string())).collect();
+    assert_eq!(batches.len(), 2);
+    assert_eq!(&batches[1][..], &["4", "5", "6", "7"]);
+
+    let mut iter = (0..10).arrayvec_chunks::<3>();
+    assert_eq!(iter.size_hint(), (4, Some(4)));
+    assert_eq!(&iter.next().unwrap()[..], &[0, 1, 2]);
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+    assert_eq!(iter.nth(2).unwrap().as_slice(), &[9]);
+    assert!(iter.next().is_none());
+    assert!(std::iter::empty::<u8>().arrayvec_chunks::<3>().next().is_none());
+}
+
+#[test]
+#[should_panic(expected="non-zero")]
+fn test_chunks_zero() {
+    arrayvec::chunks::<_, 0>(0..3);
+}
+
+#[test]
+fn test_into_capacity() {
+    let mut v = ArrayVec::<String, 4>::new();
+    v.push("a".to_string());
+    v.push("b".to_string());
+    let v = v.into_capacity::<1>().unwrap_err();
+    let v = v.into_capacity::<2>().unwrap();
+    assert_eq!(v.capacity(), 2);
+    let v = v.widen::<5>();
+    assert_eq!(&v[..], &["a", "b"]);
+    assert_eq!(v.capacity(), 5);
+
+    let s = ArrayString::<8>::from("abc").unwrap();
+    assert_eq!(s.into_capacity::<2>().unwrap_err(), s);
+    let s = s.into_capacity::<3>().unwrap().widen::<3>();
+    assert_eq!(&s[..], "abc");
+    assert_eq!(s.capacity(), 3);
+}
+
+#[test]
+fn test_try_from_iter() {
+    use arrayvec::IteratorExt;
+
+    let v = ArrayVec::<_, 3>::try_from_iter(vec![1, 2, 3]).unwrap();
+    assert_eq!(&v[..], &[1, 2, 3]);
+    let (v, overflow, rest) = ArrayVec::<_, 0>::try_from_iter(vec![1, 2]).unwrap_err();
+    assert!(v.is_empty());
+    assert_eq!(overflow, 1);
+    assert_eq!(rest.as_slice(), &[2]);
+
+    let (v, overflow, rest) = (0..10).try_collect_arrayvec::<4>().unwrap_err();
+    assert_eq!(&v[..], &[0, 1, 2, 3]);
+    assert_eq!(overflow, 4);
+    assert_eq!(rest, 5..10);
+
+    let s = "αβγ".chars().try_collect_arraystring::<6>().unwrap();
+    assert_eq!(&s[..], "αβγ");
+    let (s, overflow, rest) = "αβγ".chars().try_collect_arraystring::<5>().unwrap_err();
+    assert_eq!(&s[..], "αβ");
+    assert_eq!(overflow, 'γ');
+    assert_eq!(rest.as_str(), "");
+
+    let s = ["ab", "", "c"].iter().copied().try_concat_arraystring::<3>().unwrap();
+    assert_eq!(&s[..], "abc");
+    let (s, overflow, _) = ArrayString::<3>::try_from_strs(vec!["ab", "cd"]).unwrap_err();
+    assert_eq!(&s[..], "ab");
+    assert_eq!(overflow, "cd");
+}
+
+#[test]
+fn test_insert_slice() {
+    let mut v = ArrayVec::<_, 6>::new();
+    v.try_insert_slice(0, &[1, 5]).unwrap();
+    v.try_insert_slice(1, &[2, 3, 4]).unwrap();
+    v.try_insert_slice(5, &[]).unwrap();
+    assert_eq!(&v[..], &[1, 2, 3, 4, 5]);
+    assert!(v.try_insert_slice(0, &[0, 0]).is_err());
+    assert_eq!(&v[..], &[1, 2, 3, 4, 5]);
+
+    let mut v = ArrayVec::<String, 4>::new();
+    v.push("d".to_string());
+    v.try_insert_slice_cloned(0, &["a".to_string(), "b".to_string()]).unwrap();
+    v.insert_iter(2, Some("c".to_string()));
+    assert_eq!(&v[..], &["a", "b", "c", "d"]);
+    let err = v.try_insert_iter(4, vec!["e".to_string()]).unwrap_err();
+    assert_eq!(err.element().len(), 1);
+    assert_eq!(v.len(), 4);
+}
+
+#[test]
+#[should_panic]
+fn test_insert_slice_oob() {
+    let mut v = ArrayVec::<_, 6>::new();
+    v.push(1);
+    let _ = v.try_insert_slice(2, &[2]);
+}
+
+#[test]
+fn test_insert_iter_short() {
+    // An iterator that reports more elements than it yields
+    struct Liar(std::ops::Range<i32>);
+
+    impl Iterator for Liar {
+        type Item = i32;
+        fn next(&mut self) -> Option<i32> { self.0.next() }
+        fn size_hint(&self) -> (usize, Option<usize>) { (3, Some(3)) }
+    }
+
+    impl ExactSizeIterator for Liar {}
+
+    let mut v = ArrayVec::<_, 6>::new();
+    v.extend([0, 9].iter().copied());
+    v.insert_iter(1, Liar(1..2));
+    assert_eq!(&v[..], &[0, 1, 9]);
+}
+
+#[test]
+fn test_insert_slice_panic_safety() {
+    use std::cell::Cell;
+    use std::panic::catch_unwind;
+    use std::panic::AssertUnwindSafe;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>, i32);
+
+    impl<'a> Clone for Bump<'a> {
+        fn clone(&self) -> Self {
+            if self.1 == 2 {
+                panic!("panic in clone");
+            }
+            Bump(self.0, self.1)
+        }
+    }
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let mut v = ArrayVec::<_, 8>::new();
+    v.push(Bump(flag, 10));
+    v.push(Bump(flag, 11));
+    let insert = [Bump(flag, 0), Bump(flag, 1), Bump(flag, 2)];
+    let res = catch_unwind(AssertUnwindSafe(|| v.try_insert_slice_cloned(1, &insert)));
+    assert!(res.is_err());
+    assert_eq!(flag.get(), 0);
+    assert_eq!(v.iter().map(|elt| elt.1).collect::<Vec<_>>(), [10, 0, 1, 11]);
+    drop(v);
+    drop(insert);
+    assert_eq!(flag.get(), 7);
+}
+
+#[test]
+fn test_try_write_fmt() {
+    use arrayvec::try_push_fmt;
+
+    let mut s = ArrayString::<10>::new();
+    try_push_fmt!(s, "{}", "αβ").unwrap();
+    let err = try_push_fmt!(s, "{}{}{}", 1234, "γ", 5).unwrap_err();
+    assert_eq!((err.required(), err.available()), (7, 6));
+    assert!(!err.is_fmt_error());
+    assert_eq!(&s[..], "αβ");
+    try_push_fmt!(s, "{:>5}", "γ").unwrap();
+    assert_eq!(&s[..], "αβ    γ");
+    assert_eq!(try_push_fmt!(s, "!").unwrap_err().required(), 1);
+    s.try_write_fmt(format_args!("")).unwrap();
+    assert_eq!(s.len(), 10);
+}
+
+#[test]
+fn test_try_write_fmt_error() {
+    struct Fails;
+
+    impl std::fmt::Display for Fails {
+        fn fmt(&self, _: &mut std::fmt::Formatter) -> std::fmt::Result {
+            Err(std::fmt::Error)
+        }
+    }
+
+    let mut s = ArrayString::<10>::from("ab").unwrap();
+    let err = s.try_write_fmt(format_args!("cd{}", Fails)).unwrap_err();
+    assert!(err.is_fmt_error());
+    assert_eq!((err.required(), err.available()), (2, 8));
+    assert_eq!(err.to_string(), "a formatting trait implementation returned an error");
+    assert_eq!(&s[..], "ab");
+    assert!(ArrayString::<10>::try_from_fmt(format_args!("{}", Fails)).unwrap_err().is_fmt_error());
+}
+
+#[test]
+fn test_array_format() {
+    use arrayvec::{array_format, try_array_format};
+
+    const CAP: usize = 6;
+    let s = array_format!(CAP; "{:x}", 255);
+    assert_eq!(&s[..], "ff");
+    assert_eq!(s.capacity(), CAP);
+    let s = array_format!(2 * CAP; "{}", s.repeat(6));
+    assert_eq!(s.len(), 12);
+
+    let err = try_array_format!(CAP; "{:?}", "αβγ").unwrap_err();
+    assert_eq!((err.required(), err.available()), (8, CAP));
+    assert_eq!(err.to_string(), "insufficient capacity: formatted output needs 8 bytes, but 6 are available");
+    assert!(try_array_format!(0;"").unwrap().is_empty());
+}
+
+#[test]
+#[should_panic(expected="needs 7 bytes, but the capacity is 6")]
+fn test_array_format_overflow() {
+    arrayvec::array_format!(6; "{}", 1234567);
+}
+
+#[test]
+fn test_array_string_macro() {
+    use arrayvec::array_string;
+
+    const EMPTY: ArrayString<0> = array_string!("");
+    static GREETING: ArrayString<5> = array_string!("hello");
+    assert!(EMPTY.is_empty());
+    assert_eq!(&GREETING[..], "hello");
+    assert!(GREETING.is_full());
+
+    let mut s = array_string!(8, "hello");
+    s.push_str("!!!");
+    assert_eq!(&s[..], "hello!!!");
+    assert_eq!(ArrayString::<4>::from_str_const("ab"), ArrayString::from("ab").unwrap());
+}
+
+#[test]
+#[should_panic(expected="string does not fit")]
+fn test_from_str_const_overflow() {
+    let s = String::from("hello");
+    ArrayString::<4>::from_str_const(&s);
+}
+
+#[test]
+fn test_truncating_writer() {
+    use std::fmt::Write;
+    use arrayvec::TruncatingWriter;
+
+    // exact fit
+    let mut s = ArrayString::<6>::new();
+    let mut w = TruncatingWriter::new(&mut s);
+    let (a, b) = ("abc", "def");
+    write!(w, "{}{}", a, b).unwrap();
+    assert!(!w.is_truncated());
+    assert_eq!(&s[..], "abcdef");
+
+    // never split a char: "…" is 3 bytes, leaving 3 bytes for "αβ"
+    let mut s = ArrayString::<6>::new();
+    let mut w = TruncatingWriter::new(&mut s);
+    w.write_str("αβγ").unwrap();
+    write!(w, "ignored").unwrap();
+    assert!(w.is_truncated());
+    assert_eq!(&s[..], "α…");
+
+    // cut back text written earlier by the same writer
+    let mut s = ArrayString::<8>::from("> ").unwrap();
+    let mut w = TruncatingWriter::with_suffix(&mut s, "..");
+    let (a, b) = ("hello", "world");
+    write!(w, "{} {}", a, b).unwrap();
+    assert_eq!(&s[..], "> hell..");
+
+    // previous contents are kept, the suffix is cut
+    let mut s = ArrayString::<4>::from("abc").unwrap();
+    let mut w = TruncatingWriter::with_suffix(&mut s, "...");
+    write!(w, "de").unwrap();
+    assert!(w.is_truncated());
+    assert_eq!(&s[..], "abc.");
+}
+
+#[test]
+fn test_checkpoint() {
+    let mut v = ArrayVec::<String, 4>::new();
+    v.push("a".to_string());
+    {
+        let mut cp = v.checkpoint();
+        assert_eq!(cp.saved_len(), 1);
+        cp.push("b".to_string());
+        cp.push("c".to_string());
+        cp.rollback();
+    }
+    assert_eq!(&v[..], &["a"]);
+
+    let res: Result<(), CapacityError<String>> = v.transaction(|v| {
+        v.try_push("b".to_string())?;
+        v.try_push("c".to_string())
+    });
+    assert!(res.is_ok());
+    let res = v.transaction(|v| {
+        v.try_push("d".to_string())?;
+        v.try_push("e".to_string())
+    });
+    assert_eq!(res.unwrap_err().element(), "e");
+    assert_eq!(&v[..], &["a", "b", "c"]);
+
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        v.transaction(|v| -> Result<(), ()> {
+            v.push("d".to_string());
+            panic!("panic in transaction");
+        })
+    }));
+    assert!(res.is_err());
+    assert_eq!(&v[..], &["a", "b", "c"]);
+
+    let mut s = ArrayString::<8>::from("é").unwrap();
+    {
+        let mut cp = s.checkpoint();
+        cp.clear();
+        cp.push_str("x€");
+    }
+    assert_eq!(&s[..], "x");
+    s.transaction(|s| s.try_push_str("yz")).unwrap();
+    assert!(s.transaction(|s| { s.push('!'); s.try_push_str("longer") }).is_err());
+    assert_eq!(&s[..], "xyz");
+}
+
+#[test]
+fn test_try_extend() {
+    use std::cell::Cell;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>, i32);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let mut v = ArrayVec::<_, 4>::new();
+    v.try_extend((0..2).map(|i| Bump(flag, i))).unwrap();
+
+    // size_hint is exact: nothing is consumed
+    let consumed = &Cell::new(0);
+    let err = v.try_extend((0..3).map(|i| { consumed.set(consumed.get() + 1); Bump(flag, i) }));
+    let (overflow, rest) = err.unwrap_err().element();
+    assert!(overflow.is_none());
+    assert_eq!(rest.len(), 3);
+    assert_eq!(consumed.get(), 0);
+
+    // size_hint is not exact: roll back
+    let err = v.try_extend((2..6).filter(|_| true).map(|i| Bump(flag, i)));
+    let (overflow, mut rest) = err.unwrap_err().element();
+    assert_eq!(overflow.as_ref().map(|b| b.1), Some(4));
+    assert_eq!(rest.next().map(|b| b.1), Some(5));
+    assert_eq!(v.iter().map(|b| b.1).collect::<Vec<_>>(), [0, 1]);
+    drop(overflow);
+    assert_eq!(flag.get(), 4);
+
+    v.try_extend((2..4).filter(|_| true).map(|i| Bump(flag, i))).unwrap();
+    assert_eq!(v.iter().map(|b| b.1).collect::<Vec<_>>(), [0, 1, 2, 3]);
+    assert!(v.try_extend(std::iter::empty()).is_ok());
+}
+
+#[test]
+fn test_try_extend_panic_safety() {
+    use std::cell::Cell;
+    use std::panic::catch_unwind;
+    use std::panic::AssertUnwindSafe;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let mut v = ArrayVec::<_, 8>::new();
+    v.push(Bump(flag));
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        let _ = v.try_extend((0..4).map(|i| {
+            if i == 2 {
+                panic!("panic in try_extend iterator");
+            }
+            Bump(flag)
+        }));
+    }));
+    assert!(res.is_err());
+    // the two elements added before the panic were rolled back
+    assert_eq!(v.len(), 1);
+    assert_eq!(flag.get(), 2);
+    drop(v);
+    assert_eq!(flag.get(), 3);
+}
+
+#[test]
+fn test_extend_until_full() {
+    let mut v = ArrayVec::<String, 4>::new();
+    let words = ["a".to_string(), "b".to_string(), "c".to_string()];
+    assert!(v.extend_from_slice_truncating(&words).is_empty());
+    let rest = v.extend_from_slice_truncating(&words);
+    assert_eq!(rest, &words[1..]);
+    let rest = v.extend_until_full(rest.iter().cloned());
+    assert_eq!(rest.collect::<Vec<_>>(), ["b", "c"]);
+    assert_eq!(&v[..], &["a", "b", "c", "a"]);
+
+    v.truncate(2);
+    let rest = v.extend_until_full(words.iter().cloned());
+    assert_eq!(rest.collect::<Vec<_>>(), ["c"]);
+    assert_eq!(&v[..], &["a", "b", "a", "b"]);
+
+    let mut s = ArrayString::<5>::new();
+    assert_eq!(s.push_str_partial("ab"), "");
+    assert_eq!(s.push_str_partial("€x"), "x");
+    assert_eq!(s.push_str_partial("y"), "y");
+    assert_eq!(&s[..], "ab€");
+    s.pop();
+    assert_eq!(s.push_str_partial("x€"), "€");
+    assert_eq!(s.push_str_partial("xyz"), "z");
+    assert_eq!(&s[..], "abxxy");
+}
+
+#[test]
+fn test_extend_from_within() {
+    let mut v = ArrayVec::<String, 6>::new();
+    v.try_extend_from_slice_cloned(&["a".to_string(), "b".to_string()]).unwrap();
+    v.extend_from_within(..);
+    assert_eq!(&v[..], &["a", "b", "a", "b"]);
+    v.extend_from_within(1..=1);
+    v.extend_from_within(2..2);
+    assert_eq!(&v[..], &["a", "b", "a", "b", "b"]);
+    assert!(v.try_extend_from_within(3..).is_err());
+    assert!(v.try_extend_from_slice_cloned(&["c".to_string(), "d".to_string()]).is_err());
+    assert_eq!(v.len(), 5);
+}
+
+#[test]
+#[should_panic]
+fn test_extend_from_within_oob() {
+    let mut v = ArrayVec::<i32, 6>::new();
+    v.push(1);
+    v.extend_from_within(0..2);
+}
+
+#[test]
+fn test_extend_from_within_panic_safety() {
+    use std::cell::Cell;
+    use std::panic::catch_unwind;
+    use std::panic::AssertUnwindSafe;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>, i32);
+
+    impl<'a> Clone for Bump<'a> {
+        fn clone(&self) -> Self {
+            if self.1 == 2 {
+                panic!("panic in clone");
+            }
+            Bump(self.0, self.1)
+        }
+    }
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let mut v = ArrayVec::<_, 8>::new();
+    for i in 0..4 {
+        v.push(Bump(flag, i));
+    }
+    let res = catch_unwind(AssertUnwindSafe(|| v.extend_from_within(..)));
+    assert!(res.is_err());
+    assert_eq!(flag.get(), 0);
+    assert_eq!(v.iter().map(|elt| elt.1).collect::<Vec<_>>(), [0, 1, 2, 3, 0, 1]);
+    drop(v);
+    assert_eq!(flag.get(), 6);
+}
+
+#[test]
+fn test_resize() {
+    let mut v = ArrayVec::<String, 4>::new();
+    v.resize(3, "a".to_string());
+    assert_eq!(&v[..], &["a", "a", "a"]);
+    v.resize(1, "b".to_string());
+    assert_eq!(&v[..], &["a"]);
+    assert_eq!(v.try_resize(5, "c".to_string()).unwrap_err().element(), "c");
+    assert_eq!(&v[..], &["a"]);
+    v.resize(4, "d".to_string());
+    assert_eq!(&v[..], &["a", "d", "d", "d"]);
+
+    let mut v = ArrayVec::<_, 4>::new();
+    let mut n = 0;
+    v.resize_with(2, || { n += 1; n });
+    assert_eq!(&v[..], &[1, 2]);
+    assert!(v.try_resize_with(5, || 0).is_err());
+    v.resize_with(4, || 0);
+    assert_eq!(&v[..], &[1, 2, 0, 0]);
+    v.resize_with(0, || 0);
+    assert!(v.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_resize_overflow() {
+    let mut v = ArrayVec::<i32, 4>::new();
+    v.resize(5, 0);
+}
+
+#[test]
+fn test_split_off_append() {
+    let mut v = ArrayVec::<_, 8>::new();
+    v.extend((0..6).map(|i| i.to_string()));
+
+    assert!(v.split_off::<3>(2).is_err());
+    assert_eq!(v.len(), 6);
+    let mut tail = v.split_off::<4>(2).unwrap();
+    assert_eq!(&v[..], &["0", "1"]);
+    assert_eq!(&tail[..], &["2", "3", "4", "5"]);
+    assert!(v.split_off::<0>(2).unwrap().is_empty());
+
+    let mut small = ArrayVec::<String, 3>::new();
+    assert!(small.try_append(&mut tail).is_err());
+    assert_eq!(tail.len(), 4);
+    assert_eq!(small.move_from(&mut tail), 3);
+    assert_eq!(&small[..], &["2", "3", "4"]);
+    assert_eq!(&tail[..], &["5"]);
+    assert_eq!(small.move_from(&mut tail), 0);
+
+    v.append(&mut small);
+    v.append(&mut tail);
+    assert_eq!(&v[..], &["0", "1", "2", "3", "4", "5"]);
+    assert!(small.is_empty());
+    assert!(tail.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_split_off_oob() {
+    let mut v = ArrayVec::from([1, 2, 3]);
+    let _ = v.split_off::<3>(4);
+}
+
+#[test]
+#[should_panic]
+fn test_append_overflow() {
+    let mut v = ArrayVec::from([1, 2, 3]);
+    v.append(&mut ArrayVec::from([4]));
+}
+
+#[test]
+fn test_dedup() {
+    let mut v = ArrayVec::from([1, 1, 2, 3, 3, 3, 1, 4, 4]);
+    v.dedup();
+    assert_eq!(&v[..], &[1, 2, 3, 1, 4]);
+    v.dedup();
+    assert_eq!(&v[..], &[1, 2, 3, 1, 4]);
+
+    let mut v = ArrayVec::from([1, 2, 3, 4, 5, 6]);
+    v.dedup_by_key(|x| *x / 3);
+    assert_eq!(&v[..], &[1, 3, 6]);
+
+    let mut v = ArrayVec::from(["a", "A", "b", "B", "b", "c"]);
+    v.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    assert_eq!(&v[..], &["a", "b", "c"]);
+
+    let mut v = ArrayVec::<i32, 0>::new();
+    v.dedup();
+    assert!(v.is_empty());
+}
+
+#[test]
+fn test_dedup_panic_safety() {
+    use std::cell::Cell;
+    use std::panic::catch_unwind;
+    use std::panic::AssertUnwindSafe;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>, i32);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let mut v = ArrayVec::<_, 8>::new();
+    for &i in &[0, 0, 1, 1, 2, 2, 3, 3] {
+        v.push(Bump(flag, i));
+    }
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        v.dedup_by(|a, b| {
+            if a.1 == 2 {
+                panic!("panic in comparator");
+            }
+            a.1 == b.1
+        });
+    }));
+    assert!(res.is_err());
+    // the duplicates of 0 and 1 were dropped, the rest is kept in order
+    assert_eq!(flag.get(), 2);
+    assert_eq!(v.iter().map(|elt| elt.1).collect::<Vec<_>>(), [0, 1, 2, 2, 3, 3]);
+    drop(v);
+    assert_eq!(flag.get(), 8);
+}
+
+#[test]
+fn test_extract_if() {
+    let mut v = ArrayVec::from([0, 1, 2, 3, 4, 5, 6, 7]);
+    let odd: Vec<_> = v.extract_if(2..6, |x| *x % 2 == 1).collect();
+    assert_eq!(&odd[..], &[3, 5]);
+    assert_eq!(&v[..], &[0, 1, 2, 4, 6, 7]);
+
+    // dropped early: the rest is kept
+    let mut iter = v.extract_if(.., |x| { *x += 10; *x > 11 });
+    assert_eq!(iter.next(), Some(12));
+    drop(iter);
+    assert_eq!(&v[..], &[10, 11, 4, 6, 7]);
+
+    let none: Vec<_> = v.extract_if(.., |_| false).collect();
+    assert!(none.is_empty());
+    assert_eq!(&v[..], &[10, 11, 4, 6, 7]);
+    let all: Vec<_> = v.extract_if(.., |_| true).collect();
+    assert_eq!(&all[..], &[10, 11, 4, 6, 7]);
+    assert!(v.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_extract_if_oob() {
+    let mut v = ArrayVec::from([0; 8]);
+    v.pop();
+    v.extract_if(0..8, |_| true);
+}
+
+#[test]
+fn test_extract_if_panic_safety() {
+    use std::cell::Cell;
+    use std::panic::catch_unwind;
+    use std::panic::AssertUnwindSafe;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>, i32);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let mut v = ArrayVec::<_, 8>::new();
+    for i in 0..8 {
+        v.push(Bump(flag, i));
+    }
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        for elt in v.extract_if(.., |elt| {
+            if elt.1 == 5 {
+                panic!("panic in predicate");
+            }
+            elt.1 % 2 == 0
+        }) {
+            drop(elt);
+        }
+    }));
+    assert!(res.is_err());
+    // 0, 2 and 4 were extracted and dropped, the rest is kept in order
+    assert_eq!(flag.get(), 3);
+    assert_eq!(v.iter().map(|elt| elt.1).collect::<Vec<_>>(), [1, 3, 5, 6, 7]);
+    drop(v);
+    assert_eq!(flag.get(), 8);
+}
+
 #[test]
 fn test_retain() {
     let mut v = ArrayVec::from([0; 8]);
@@ -776,20 +1772,46 @@ fn allow_max_capacity_arrayvec_type() {
     let _v: ArrayVec<(), {usize::MAX}>;
 }
 
-#[should_panic(expected="largest supported capacity")]
-#[cfg(not(any(target_pointer_width = "16", target_pointer_width = "32")))]
 #[test]
-fn deny_max_capacity_arrayvec_value() {
-    // this type is allowed to be used (but can't be constructed)
-    let _v: ArrayVec<(), {usize::MAX}> = ArrayVec::new();
+fn test_len_type() {
+    use std::mem::size_of;
+
+    assert_eq!(size_of::<ArrayVec<u8, 15, u8>>(), 16);
+    assert_eq!(size_of::<ArrayVec<u8, 14, u16>>(), 16);
+    assert_eq!(size_of::<ArrayString<7, u8>>(), 8);
+
+    let mut v = ArrayVec::<u8, 255, u8>::new();
+    v.extend(0..=254);
+    assert!(v.is_full());
+    assert_eq!(v.len(), 255);
+    assert_eq!(v.pop(), Some(254));
+    v.drain(10..);
+    assert_eq!(&v[..], &(0..10).collect::<Vec<_>>()[..]);
+    let mut v: ArrayVec<u8, 300, u16> = v.into_iter().collect();
+    v.retain(|x| *x % 2 == 0);
+    assert_eq!(&v[..], &[0, 2, 4, 6, 8]);
+
+    let mut s = ArrayString::<210, u8>::new();
+    for _ in 0..30 {
+        s.push_str("abcd€");
+    }
+    assert!(s.is_full());
+    assert_eq!(s.len(), 210);
+    assert_eq!(s.pop(), Some('€'));
+    assert_eq!(s.len(), 207);
 }
 
-#[should_panic(expected="index out of bounds")]
-#[cfg(not(any(target_pointer_width = "16", target_pointer_width = "32")))]
 #[test]
-fn deny_max_capacity_arrayvec_value_const() {
-    // this type is allowed to be used (but can't be constructed)
-    let _v: ArrayVec<(), {usize::MAX}> = ArrayVec::new_const();
+#[cfg(target_pointer_width = "64")]
+fn test_len_type_usize() {
+    let mut v = ArrayVec::<(), { u32::MAX as usize + 1 }, usize>::new();
+    unsafe {
+        v.set_len(u32::MAX as usize + 1);
+    }
+    assert!(v.is_full());
+    assert_eq!(v.len(), u32::MAX as usize + 1);
+    assert_eq!(v.pop(), Some(()));
+    assert_eq!(v.len(), u32::MAX as usize);
 }
 
 #[test]
@@ -821,3 +1843,229 @@ fn test_arraystring_zero_filled_has_some_sanity_checks() {
     assert_eq!(string.as_str(), "\0\0\0\0");
     assert_eq!(string.len(), 4);
 }
+
+#[test]
+fn test_deque_push_pop() {
+    let mut deque = ArrayDeque::<_, 4>::new();
+    deque.push_back(2);
+    deque.push_back(3);
+    deque.push_front(1);
+    deque.push_front(0);
+    assert!(deque.is_full());
+    assert!(deque.try_push_back(4).is_err());
+    assert!(deque.try_push_front(4).is_err());
+    assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+    assert_eq!(deque.pop_back(), Some(3));
+    assert_eq!(deque.pop_front(), Some(0));
+    assert_eq!((deque.front(), deque.back()), (Some(&1), Some(&2)));
+
+    // wrap around the end of the buffer several times
+    for i in 3..20 {
+        deque.push_back(i);
+        assert_eq!(deque.pop_front(), Some(i - 2));
+        assert_eq!(deque.len(), 2);
+    }
+    assert_eq!(deque.iter().rev().copied().collect::<Vec<_>>(), [19, 18]);
+    assert_eq!(deque[0], 18);
+
+    let mut empty = ArrayDeque::<i32, 0>::new();
+    assert!(empty.try_push_front(1).is_err());
+    assert_eq!(empty.push_back_overwrite(1), Some(1));
+    assert_eq!(empty.pop_back(), None);
+}
+
+#[test]
+fn test_deque_overwrite() {
+    let mut deque = ArrayDeque::<_, 3>::new();
+    for i in 0..3 {
+        assert_eq!(deque.push_back_overwrite(i), None);
+    }
+    for i in 3..10 {
+        assert_eq!(deque.push_back_overwrite(i), Some(i - 3));
+    }
+    assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [7, 8, 9]);
+}
+
+#[test]
+fn test_deque_contiguous() {
+    let mut deque = ArrayDeque::<_, 5>::new();
+    deque.extend(0..5);
+    for _ in 0..3 {
+        deque.pop_front();
+    }
+    deque.extend(5..8);
+    assert_eq!(deque.as_slices(), (&[3, 4][..], &[5, 6, 7][..]));
+    assert_eq!(deque.make_contiguous(), &[3, 4, 5, 6, 7]);
+    assert_eq!(deque.as_slices(), (&[3, 4, 5, 6, 7][..], &[][..]));
+}
+
+#[test]
+fn test_deque_drain() {
+    for &(range_start, range_end) in &[(0, 0), (0, 5), (1, 3), (2, 5), (0, 2), (4, 5)] {
+        for head in 0..5 {
+            // rotate the contents so that they start at `head`
+            let mut deque = ArrayDeque::<_, 5>::new();
+            for _ in 0..head {
+                deque.push_back(0);
+                deque.pop_front();
+            }
+            deque.extend(0..5);
+            let drained: Vec<_> = deque.drain(range_start..range_end).collect();
+            assert_eq!(drained, (range_start..range_end).collect::<Vec<_>>());
+            let expected: Vec<_> = (0..range_start).chain(range_end..5).collect();
+            assert_eq!(deque.iter().copied().collect::<Vec<_>>(), expected);
+        }
+    }
+
+    // all ranges of a partially filled deque, moving either the front or the tail
+    for range_start in 0..=6 {
+        for range_end in range_start..=6 {
+            for head in 0..8 {
+                let mut deque = ArrayDeque::<_, 8>::new();
+                for _ in 0..head {
+                    deque.push_back(0);
+                    deque.pop_front();
+                }
+                deque.extend(0..6);
+                deque.drain(range_start..range_end);
+                let expected: Vec<_> = (0..range_start).chain(range_end..6).collect();
+                assert_eq!(deque.iter().copied().collect::<Vec<_>>(), expected);
+                deque.extend(10..10 + range_end - range_start + 2);
+                assert!(deque.is_full());
+                assert_eq!(deque.pop_back(), Some(11 + range_end - range_start));
+            }
+        }
+    }
+
+    let mut deque = ArrayDeque::from([0, 1, 2, 3, 4]);
+    {
+        let mut drain = deque.drain(1..4);
+        assert_eq!(drain.next_back(), Some(3));
+        assert_eq!(drain.next(), Some(1));
+    }
+    assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [0, 4]);
+}
+
+#[test]
+#[should_panic]
+fn test_deque_drain_oob() {
+    let mut deque = ArrayDeque::from([0; 8]);
+    deque.pop_back();
+    deque.drain(0..8);
+}
+
+#[test]
+fn test_deque_drop() {
+    use std::cell::Cell;
+
+    let flag = &Cell::new(0);
+
+    #[derive(Clone)]
+    struct Bump<'a>(&'a Cell<i32>);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    {
+        // elements wrapping around the end of the buffer
+        let mut deque = ArrayDeque::<_, 4>::new();
+        deque.push_back(Bump(flag));
+        deque.push_back(Bump(flag));
+        deque.push_front(Bump(flag));
+        deque.push_front(Bump(flag));
+        assert_eq!(flag.get(), 0);
+        drop(deque.pop_front());
+        assert_eq!(flag.get(), 1);
+        deque.truncate(1);
+        assert_eq!(flag.get(), 3);
+        drop(deque.push_back_overwrite(Bump(flag)));
+        drop(deque.push_back_overwrite(Bump(flag)));
+        drop(deque.push_back_overwrite(Bump(flag)));
+        assert_eq!(flag.get(), 3);
+        drop(deque.push_back_overwrite(Bump(flag)));
+        assert_eq!(flag.get(), 4);
+    }
+    assert_eq!(flag.get(), 8);
+
+    flag.set(0);
+    {
+        let mut deque = ArrayDeque::<_, 4>::new();
+        deque.push_back(Bump(flag));
+        deque.push_front(Bump(flag));
+        deque.push_front(Bump(flag));
+        let mut iter = deque.into_iter();
+        iter.next();
+        assert_eq!(flag.get(), 1);
+        let clone = iter.clone();
+        drop(clone);
+        assert_eq!(flag.get(), 3);
+        drop(iter);
+        assert_eq!(flag.get(), 5);
+    }
+
+    flag.set(0);
+    {
+        let mut deque = ArrayDeque::<_, 4>::new();
+        deque.push_back(Bump(flag));
+        deque.push_back(Bump(flag));
+        deque.push_front(Bump(flag));
+        deque.push_front(Bump(flag));
+        let mut drain = deque.drain(1..3);
+        drain.next();
+        assert_eq!(flag.get(), 1);
+        drop(drain);
+        assert_eq!(flag.get(), 2);
+        assert_eq!(deque.len(), 2);
+    }
+    assert_eq!(flag.get(), 4);
+}
+
+#[test]
+fn test_deque_traits() {
+    let mut a = ArrayDeque::<_, 4>::new();
+    a.push_back(2);
+    a.push_front(1);
+    let b: ArrayDeque<_, 4> = vec![1, 2].into_iter().collect();
+    assert_eq!(a, b);
+    assert_eq!(a.clone(), b);
+    assert_eq!(format!("{:?}", a), "[1, 2]");
+
+    let mut map = HashMap::new();
+    map.insert(a, 1);
+    assert_eq!(map[&b], 1);
+}
+
+#[test]
+fn test_deque_hash_wrapped() {
+    use std::hash::{Hash, Hasher};
+
+    // Records the boundaries of the writes, not just the bytes
+    #[derive(Default)]
+    struct WriteLog(Vec<Vec<u8>>);
+
+    impl Hasher for WriteLog {
+        fn finish(&self) -> u64 { 0 }
+        fn write(&mut self, bytes: &[u8]) {
+            self.0.push(bytes.to_vec());
+        }
+    }
+
+    let a: ArrayDeque<u8, 4> = vec![1, 2, 3].into_iter().collect();
+    let mut b = ArrayDeque::<u8, 4>::new();
+    b.push_back(0);
+    b.push_back(0);
+    b.pop_front();
+    b.pop_front();
+    b.extend(vec![1, 2, 3]);
+    assert_ne!(a.as_slices().0.len(), b.as_slices().0.len());
+    assert_eq!(a, b);
+
+    let (mut ha, mut hb) = (WriteLog::default(), WriteLog::default());
+    a.hash(&mut ha);
+    b.hash(&mut hb);
+    assert_eq!(ha.0, hb.0);
+}